@@ -0,0 +1,263 @@
+//! The command line the day binaries share.
+
+use crate::input::Source;
+use crate::parse::ParseError;
+use crate::solution::Solution;
+use std::io::BufRead;
+use std::path::Path;
+use std::process;
+use std::time::{Duration, Instant};
+
+/// How the answers are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// `P1: ...` and `P2: ...` on lines of their own
+    Text,
+    /// A single JSON object
+    Json,
+}
+
+impl std::str::FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Format, String> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("Format must be text or json, got {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    pub format: Format,
+    pub verbosity: u8,
+    /// Stdin unless `--input <PATH>` or `--sample` says otherwise
+    pub source: Source,
+}
+
+pub const USAGE: &str = "Options: [--input <PATH> | --sample | -] [--format text|json] [-v|-vv]";
+
+impl Options {
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Options, String> {
+        let mut options = Options {
+            format: Format::Text,
+            verbosity: 0,
+            source: Source::Stdin,
+        };
+
+        let mut source = None;
+        let mut set_source = |new_source| match source.replace(new_source) {
+            Some(_) => Err("Only one of --input, --sample and - can be given"),
+            None => Ok(()),
+        };
+
+        let mut iter = args.into_iter();
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "--format" => {
+                    let value = iter.next().ok_or("Missing value for --format")?;
+                    options.format = value.parse()?;
+                }
+                "-v" | "--verbose" => options.verbosity += 1,
+                "-vv" => options.verbosity += 2,
+                "--input" => {
+                    let value = iter.next().ok_or("Missing value for --input")?;
+                    set_source(Source::Path(value.into()))?;
+                }
+                "--sample" => set_source(Source::Sample)?,
+                "-" => set_source(Source::Stdin)?,
+                _ => return Err(format!("Unknown argument: {}", arg)),
+            }
+        }
+
+        if let Some(source) = source {
+            options.source = source;
+        }
+        Ok(options)
+    }
+
+    /// The options of the running binary. Sets the verbosity of the log, and
+    /// exits with the usage on bad options.
+    pub fn from_env() -> Options {
+        Options::from_args(std::env::args().skip(1))
+    }
+
+    /// Like `from_env`, for binaries that take some arguments of their own
+    /// out first.
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Options {
+        match Options::parse(args) {
+            Ok(options) => {
+                crate::log::set_verbosity(options.verbosity);
+                options
+            }
+            Err(message) => {
+                eprintln!("{}\n\n{}", message, USAGE);
+                process::exit(2);
+            }
+        }
+    }
+}
+
+/// The answers of a day, and how long it took to get them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Answers {
+    pub day: usize,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+    pub elapsed: Duration,
+}
+
+impl Answers {
+    /// `P1: ...` and `P2: ...` for the parts there are answers for.
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for (name, answer) in [("P1", &self.part1), ("P2", &self.part2)] {
+            if let Some(answer) = answer {
+                text += &format!("{}: {}\n", name, answer);
+            }
+        }
+        text
+    }
+
+    /// A single line of JSON. The answers are strings, or null for a part
+    /// that wasn't run.
+    pub fn to_json(&self) -> String {
+        let answer = |answer: &Option<String>| answer.as_deref().map_or("null".to_string(), json_string);
+        format!(
+            r#"{{"day": {}, "part1": {}, "part2": {}, "elapsed_ms": {:.3}}}"#,
+            self.day,
+            answer(&self.part1),
+            answer(&self.part2),
+            self.elapsed.as_secs_f64() * 1000.0
+        )
+    }
+
+    pub fn format(&self, format: Format) -> String {
+        match format {
+            Format::Text => self.to_text(),
+            Format::Json => self.to_json() + "\n",
+        }
+    }
+}
+
+/// What the day binaries do: reads the input from where the options say,
+/// solves the parts the day has and prints the answers in the format asked
+/// for. `day_dir` is the directory of the day's crate, which holds its sample
+/// and puzzle input. Input that can't be read or doesn't parse gets a message
+/// on stderr and a failing exit code instead.
+pub fn run(day: usize, solution: &dyn Solution, day_dir: &str, options: &Options) {
+    let (source, input) = read_input(day_dir, options);
+    let input = input.as_str();
+
+    let start = Instant::now();
+    let answers = solution.part1(input).and_then(|part1| {
+        let part2 = match solution.parts() {
+            1 => None,
+            _ => Some(solution.part2(input)?),
+        };
+        Ok((part1, part2))
+    });
+
+    match answers {
+        Ok((part1, part2)) => {
+            let answers = Answers {
+                day,
+                part1: Some(part1),
+                part2,
+                elapsed: start.elapsed(),
+            };
+            print!("{}", answers.format(options.format));
+        }
+        Err(err) => fail(&err, &source, input),
+    }
+}
+
+/// The input the options ask for and the name to show for it in messages.
+/// Exits if it can't be read.
+pub fn read_input(day_dir: &str, options: &Options) -> (String, String) {
+    options.source.read(Path::new(day_dir)).unwrap_or_else(|message| {
+        eprintln!("{}", message);
+        process::exit(1);
+    })
+}
+
+/// Like `read_input`, but opens the input to be read line by line.
+pub fn open_input(day_dir: &str, options: &Options) -> (String, Box<dyn BufRead>) {
+    options.source.open(Path::new(day_dir)).unwrap_or_else(|message| {
+        eprintln!("{}", message);
+        process::exit(1);
+    })
+}
+
+/// Shows where the input named `source` doesn't parse and exits.
+pub fn fail(err: &ParseError, source: &str, input: &str) -> ! {
+    eprint!("{}", err.report(source, input));
+    process::exit(1);
+}
+
+pub(crate) fn json_string(s: &str) -> String {
+    let mut result = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            c if c.is_control() => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> Result<Options, String> {
+        Options::parse(args.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_parse() {
+        let options = parse("--format json -v --sample").unwrap();
+        assert_eq!(options.format, Format::Json);
+        assert_eq!(options.verbosity, 1);
+        assert_eq!(options.source, Source::Sample);
+
+        assert_eq!(parse("").unwrap().source, Source::Stdin);
+        assert_eq!(parse("-").unwrap().source, Source::Stdin);
+        assert_eq!(parse("--input d1/input").unwrap().source, Source::Path("d1/input".into()));
+        assert!(parse("--input").is_err());
+        assert!(parse("--input d1/input --sample").is_err());
+        assert!(parse("--sample -").is_err());
+        assert!(parse("sample").is_err());
+
+        assert_eq!(parse("-vv").unwrap().verbosity, 2);
+        assert_eq!(parse("").unwrap().format, Format::Text);
+        assert!(parse("--format yaml").is_err());
+        assert!(parse("--format").is_err());
+    }
+
+    #[test]
+    fn test_answers() {
+        let answers = Answers {
+            day: 10,
+            part1: Some("13140".into()),
+            part2: Some("##..\n#\"\\".into()),
+            elapsed: Duration::from_micros(1500),
+        };
+        assert_eq!(answers.to_text(), "P1: 13140\nP2: ##..\n#\"\\\n");
+        assert_eq!(
+            answers.to_json(),
+            r###"{"day": 10, "part1": "13140", "part2": "##..\n#\"\\", "elapsed_ms": 1.500}"###
+        );
+
+        let answers = Answers { part2: None, ..answers };
+        assert_eq!(answers.to_text(), "P1: 13140\n");
+        assert!(answers.to_json().contains(r#""part2": null"#));
+    }
+}
@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// From step `start` on, the simulation repeats itself every `length` steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+/// What a simulation did until its state repeated: the delta of every step
+/// and the cycle, if one was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct History {
+    pub deltas: Vec<i64>,
+    pub cycle: Option<Cycle>,
+}
+
+impl History {
+    /// The sum of the deltas over the first `steps` steps. Steps past the
+    /// ones simulated are worked out from the cycle; panics without one.
+    pub fn total(&self, steps: u64) -> i64 {
+        if steps <= self.deltas.len() as u64 {
+            return self.deltas[..steps as usize].iter().sum();
+        }
+
+        let Cycle { start, length } = self
+            .cycle
+            .expect("Asked for more steps than were simulated, but there is no cycle");
+
+        let before: i64 = self.deltas[..start].iter().sum();
+        let per_cycle: i64 = self.deltas[start..start + length].iter().sum();
+
+        let remaining = steps - start as u64;
+        let cycles = (remaining / length as u64) as i64;
+        let rest = (remaining % length as u64) as usize;
+        let after: i64 = self.deltas[start..start + rest].iter().sum();
+
+        before + cycles * per_cycle + after
+    }
+}
+
+/// Runs `step` on `state` until `fingerprint` gives a value it gave before,
+/// or for `limit` steps. `step` returns the delta of a metric, like how much
+/// something grew. States with equal fingerprints have to behave the same
+/// from there on.
+pub fn find<S, K: Hash + Eq>(
+    mut state: S,
+    limit: u64,
+    mut step: impl FnMut(&mut S) -> i64,
+    mut fingerprint: impl FnMut(&S) -> K,
+) -> History {
+    let mut seen = HashMap::new();
+    let mut deltas = Vec::new();
+
+    for i in 0..limit as usize {
+        if let Some(start) = seen.insert(fingerprint(&state), i) {
+            let cycle = Cycle { start, length: i - start };
+            return History { deltas, cycle: Some(cycle) };
+        }
+        deltas.push(step(&mut state));
+    }
+
+    History { deltas, cycle: None }
+}
+
+/// The sum of the deltas `step` returns over `steps` steps, simulating only
+/// until the state repeats. None if it didn't repeat within `limit` steps,
+/// which keeps a fingerprint that never repeats from eating all memory.
+pub fn extrapolate<S, K: Hash + Eq>(
+    state: S,
+    steps: u64,
+    limit: u64,
+    step: impl FnMut(&mut S) -> i64,
+    fingerprint: impl FnMut(&S) -> K,
+) -> Option<i64> {
+    let history = find(state, steps.min(limit), step, fingerprint);
+    if history.cycle.is_none() && (history.deltas.len() as u64) < steps {
+        return None;
+    }
+    Some(history.total(steps))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Walks 0, 1, 2, ..., 9, 4, 5, ..., 9, 4, ... adding the position every step
+    fn step(position: &mut u64) -> i64 {
+        *position = if *position == 9 { 4 } else { *position + 1 };
+        *position as i64
+    }
+
+    fn simulated(steps: u64) -> i64 {
+        let mut position = 0;
+        (0..steps).map(|_| step(&mut position)).sum()
+    }
+
+    #[test]
+    fn test_find() {
+        let history = find(0, 100, step, |p| *p);
+        assert_eq!(history.cycle, Some(Cycle { start: 4, length: 6 }));
+        assert_eq!(history.deltas, vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 4]);
+
+        let history = find(0, 5, step, |p| *p);
+        assert_eq!(history.cycle, None);
+        assert_eq!(history.total(3), 6);
+    }
+
+    #[test]
+    fn test_extrapolate() {
+        for steps in [0, 1, 4, 9, 10, 11, 100, 1001] {
+            assert_eq!(extrapolate(0, steps, 100, step, |p| *p), Some(simulated(steps)), "{} steps", steps);
+        }
+
+        // 4 steps before the cycle, then 39 per 6 steps
+        let steps = 1_000_000_000_000;
+        let cycles = (steps - 4) / 6;
+        assert_eq!(extrapolate(0, steps, 100, step, |p| *p), Some(10 + cycles as i64 * 39));
+
+        // The cycle only shows after 10 steps, but a few steps need no cycle
+        assert_eq!(extrapolate(0, steps, 9, step, |p| *p), None);
+        assert_eq!(extrapolate(0, 9, 9, step, |p| *p), Some(simulated(9)));
+        assert_eq!(extrapolate(0, 1_000, 0, |p: &mut u64| { *p += 1; 1 }, |p| *p), None);
+    }
+}
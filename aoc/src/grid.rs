@@ -0,0 +1,326 @@
+use crate::parse::{self, ParseError};
+use crate::Point;
+use std::ops::{Index, IndexMut};
+
+/// Parses a character map into rows, converting every cell with `f`. A cell
+/// that `f` returns `None` for is reported as not being `expected`.
+pub fn parse_grid<T>(
+    input: &str,
+    expected: &str,
+    mut f: impl FnMut(char) -> Option<T>,
+) -> Result<Vec<Vec<T>>, ParseError> {
+    parse::lines(input, |cursor| {
+        let mut row = Vec::new();
+        while !cursor.is_empty() {
+            let start = cursor.clone();
+            let cell = f(cursor.next_char()?).ok_or_else(|| start.error(expected))?;
+            row.push(cell);
+        }
+        Ok(row)
+    })
+}
+
+/// Checks that the grid has at least one row and that all rows are as long as
+/// the first one.
+pub fn ensure_rectangular<T>(input: &str, grid: &[Vec<T>]) -> Result<(), ParseError> {
+    let width = match grid.first() {
+        Some(row) => row.len(),
+        None => return Err(ParseError::end_of_input(input, "a row")),
+    };
+
+    for (i, row) in grid.iter().enumerate() {
+        if row.len() != width {
+            let found = if row.len() < width { "end of line" } else { "another cell" };
+            let column = row.len().min(width) + 1;
+            return Err(ParseError::new(i + 1, column, "a row as long as the first", found));
+        }
+    }
+
+    Ok(())
+}
+
+/// What happens to positions that fall off the edge of a [`Grid`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bounds {
+    /// They are left out.
+    Clip,
+    /// They come back in on the opposite edge.
+    Wrap,
+}
+
+/// A rectangular map stored row by row. Cells are addressed by [`Point`], or
+/// by a `(row, column)` pair of indices.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    height: usize,
+    width: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A grid with every cell set to `value`.
+    pub fn new(height: usize, width: usize, value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            height,
+            width,
+            cells: vec![value; height * width],
+        }
+    }
+
+    /// Panics if the rows are not all the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Grid<T> {
+        let width = rows.first().map_or(0, |row| row.len());
+        assert!(
+            rows.iter().all(|row| row.len() == width),
+            "The rows of a grid have to be the same length"
+        );
+
+        Grid {
+            height: rows.len(),
+            width,
+            cells: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    /// Parses a character map, see [`parse_grid`]. The map has to be rectangular.
+    pub fn parse(
+        input: &str,
+        expected: &str,
+        f: impl FnMut(char) -> Option<T>,
+    ) -> Result<Grid<T>, ParseError> {
+        let rows = parse_grid(input, expected, f)?;
+        ensure_rectangular(input, &rows)?;
+        Ok(Grid::from_rows(rows))
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        (0..self.height as i32).contains(&point.row) && (0..self.width as i32).contains(&point.col)
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.contains(point)
+            .then(|| &self.cells[point.row as usize * self.width + point.col as usize])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        if self.contains(point) {
+            Some(&mut self.cells[point.row as usize * self.width + point.col as usize])
+        } else {
+            None
+        }
+    }
+
+    /// Brings a position from outside the grid back in on the opposite edge.
+    pub fn wrap(&self, point: Point) -> Point {
+        Point::new(
+            point.row.rem_euclid(self.height as i32),
+            point.col.rem_euclid(self.width as i32),
+        )
+    }
+
+    /// The position inside the grid that `point` ends up at, if any.
+    pub fn bounded(&self, point: Point, bounds: Bounds) -> Option<Point> {
+        match bounds {
+            Bounds::Clip => Some(point).filter(|p| self.contains(*p)),
+            Bounds::Wrap => Some(self.wrap(point)),
+        }
+    }
+
+    /// The cells up, down, left and right of `point`.
+    pub fn neighbors4(&self, point: Point, bounds: Bounds) -> impl Iterator<Item = Point> + '_ {
+        point
+            .neighbors4()
+            .into_iter()
+            .filter_map(move |p| self.bounded(p, bounds))
+    }
+
+    /// The cells around `point`, diagonals included.
+    pub fn neighbors8(&self, point: Point, bounds: Bounds) -> impl Iterator<Item = Point> + '_ {
+        point
+            .neighbors8()
+            .into_iter()
+            .filter_map(move |p| self.bounded(p, bounds))
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        &mut self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    /// The cells of a column, from top to bottom.
+    pub fn column(&self, col: usize) -> impl DoubleEndedIterator<Item = &T> + '_ {
+        assert!(col < self.width, "Column {} is outside of the grid", col);
+        (0..self.height).map(move |row| &self.cells[row * self.width + col])
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + '_ {
+        (0..self.height).map(|row| self.row(row))
+    }
+
+    pub fn columns(&self) -> impl DoubleEndedIterator<Item = impl DoubleEndedIterator<Item = &T>> + '_ {
+        (0..self.width).map(|col| self.column(col))
+    }
+
+    /// Adds a row at the bottom, it has to be as wide as the grid.
+    pub fn push_row(&mut self, row: impl IntoIterator<Item = T>) {
+        let len = self.cells.len();
+        self.cells.extend(row);
+        assert_eq!(self.cells.len() - len, self.width, "The new row has the wrong length");
+        self.height += 1;
+    }
+
+    /// Every position in the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height * width).map(move |i| Point::new((i / width) as i32, (i % width) as i32))
+    }
+
+    /// Every cell together with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    /// The position of the first cell, row by row, that matches `predicate`.
+    pub fn position(&self, predicate: impl Fn(&T) -> bool) -> Option<Point> {
+        self.iter().find(|(_, cell)| predicate(cell)).map(|(p, _)| p)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            height: self.height,
+            width: self.width,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Draws the grid with one character per cell and a newline after every row.
+    pub fn render(&self, mut f: impl FnMut(&T) -> char) -> String {
+        let mut result = String::with_capacity(self.height * (self.width + 1));
+        for row in self.rows() {
+            result.extend(row.iter().map(&mut f));
+            result.push('\n');
+        }
+        result
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{:?} is outside of the {}x{} grid", point, self.height, self.width))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        let (height, width) = (self.height, self.width);
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{:?} is outside of the {}x{} grid", point, height, width))
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        &self.row(row)[col]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        &mut self.row_mut(row)[col]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_grid() {
+        let grid = parse_grid("12\n34\n", "a digit", |c| c.to_digit(10));
+        assert_eq!(grid, Ok(vec![vec![1, 2], vec![3, 4]]));
+
+        let grid = parse_grid("12\n3x\n", "a digit", |c| c.to_digit(10));
+        assert_eq!(grid, Err(ParseError::new(2, 2, "a digit", "\"x\"")));
+    }
+
+    #[test]
+    fn test_ensure_rectangular() {
+        let grid = parse_grid("12\n34\n", "a digit", |c| c.to_digit(10)).unwrap();
+        assert_eq!(ensure_rectangular("", &grid), Ok(()));
+
+        let input = "12\n345\n";
+        let grid = parse_grid(input, "a digit", |c| c.to_digit(10)).unwrap();
+        assert_eq!(
+            ensure_rectangular(input, &grid),
+            Err(ParseError::new(2, 3, "a row as long as the first", "another cell"))
+        );
+    }
+
+    #[test]
+    fn test_find() {
+        let grid = Grid::parse(".#.\n#..\n", "a cell", Some).unwrap();
+        let found: Vec<Point> = grid.iter().filter(|(_, &c)| c == '#').map(|(p, _)| p).collect();
+        assert_eq!(found, vec![Point::new(0, 1), Point::new(1, 0)]);
+        assert_eq!(grid.position(|&c| c == 'x'), None);
+    }
+
+    #[test]
+    fn test_grid() {
+        let input = "12\n34\n56\n";
+        let mut grid = Grid::parse(input, "a digit", |c| c.to_digit(10)).unwrap();
+        assert_eq!((grid.height(), grid.width()), (3, 2));
+        assert_eq!(grid[Point::new(1, 0)], 3);
+        assert_eq!(grid[(2, 1)], 6);
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.position(|&d| d > 3), Some(Point::new(1, 1)));
+
+        assert_eq!(grid.row(1), &[3, 4]);
+        assert_eq!(grid.column(1).rev().collect::<Vec<_>>(), vec![&6, &4, &2]);
+        assert_eq!(grid.columns().count(), 2);
+
+        grid[Point::new(0, 0)] = 7;
+        grid.push_row([8, 9]);
+        assert_eq!(grid.render(|d| char::from_digit(*d, 10).unwrap()), "72\n34\n56\n89\n");
+
+        assert_eq!(
+            Grid::parse("12\n3\n", "a digit", |c| c.to_digit(10)),
+            Err(ParseError::new(2, 2, "a row as long as the first", "end of line"))
+        );
+    }
+
+    #[test]
+    fn test_grid_neighbors() {
+        let grid = Grid::new(3, 4, '.');
+        let corner = Point::new(0, 0);
+
+        let clipped: Vec<Point> = grid.neighbors4(corner, Bounds::Clip).collect();
+        assert_eq!(clipped, vec![Point::new(1, 0), Point::new(0, 1)]);
+        assert_eq!(grid.neighbors8(corner, Bounds::Clip).count(), 3);
+
+        let wrapped: Vec<Point> = grid.neighbors4(corner, Bounds::Wrap).collect();
+        assert_eq!(
+            wrapped,
+            vec![Point::new(2, 0), Point::new(1, 0), Point::new(0, 3), Point::new(0, 1)]
+        );
+        assert_eq!(grid.neighbors8(corner, Bounds::Wrap).count(), 8);
+    }
+}
@@ -0,0 +1,78 @@
+use std::fs;
+use std::io::{self, BufRead, Read};
+use std::path::{Path, PathBuf};
+
+/// Where a day's input comes from. The sample and the puzzle input are read
+/// from the day's directory when the program runs, never built into it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    /// Standard input, `-` on the command line
+    Stdin,
+    /// A file, `--input <PATH>`
+    Path(PathBuf),
+    /// The sample from the puzzle text, `--sample`
+    Sample,
+    /// The day's puzzle input
+    Puzzle,
+}
+
+impl Source {
+    /// Where the input is read from, given the directory of the day's crate.
+    /// None for stdin.
+    pub fn path(&self, day_dir: &Path) -> Option<PathBuf> {
+        match self {
+            Source::Stdin => None,
+            Source::Path(path) => Some(path.clone()),
+            Source::Sample => Some(day_dir.join("sample.txt")),
+            Source::Puzzle => Some(day_dir.join("input")),
+        }
+    }
+
+    /// Opens the input for reading line by line, returning the name to show
+    /// for it in messages along with it.
+    pub fn open(&self, day_dir: &Path) -> Result<(String, Box<dyn BufRead>), String> {
+        match self.path(day_dir) {
+            None => Ok(("stdin".to_string(), Box::new(io::stdin().lock()))),
+            Some(path) => match fs::File::open(&path) {
+                Ok(file) => Ok((path.display().to_string(), Box::new(io::BufReader::new(file)))),
+                Err(err) => Err(format!("Can't read {}: {}", path.display(), err)),
+            },
+        }
+    }
+
+    /// Reads the input, returning the name to show for it in messages along
+    /// with it. The error says which file couldn't be read.
+    pub fn read(&self, day_dir: &Path) -> Result<(String, String), String> {
+        match self.path(day_dir) {
+            None => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(|err| format!("Can't read stdin: {}", err))?;
+                Ok(("stdin".to_string(), input))
+            }
+            Some(path) => match fs::read_to_string(&path) {
+                Ok(input) => Ok((path.display().to_string(), input)),
+                Err(err) => Err(format!("Can't read {}: {}", path.display(), err)),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path() {
+        let dir = Path::new("d7");
+        assert_eq!(Source::Stdin.path(dir), None);
+        assert_eq!(Source::Path("x.txt".into()).path(dir), Some(PathBuf::from("x.txt")));
+        assert_eq!(Source::Sample.path(dir), Some(PathBuf::from("d7/sample.txt")));
+        assert_eq!(Source::Puzzle.path(dir), Some(PathBuf::from("d7/input")));
+
+        let err = Source::Path("no/such/file".into()).read(dir).unwrap_err();
+        assert!(err.starts_with("Can't read no/such/file: "), "{}", err);
+        assert!(Source::Path("no/such/file".into()).open(dir).is_err());
+    }
+}
@@ -0,0 +1,24 @@
+//! Plumbing shared by the day crates: input loading and parsing, coordinate
+//! types, a grid for the character maps, graph searches, cycle detection for
+//! long simulations, a recorder for watching them, a seeded random number
+//! generator for making up inputs, the `Solution` trait the runner calls every
+//! day through and the command line of the day binaries.
+
+pub mod cli;
+pub mod cycle;
+pub mod grid;
+pub mod input;
+pub mod log;
+pub mod parse;
+pub mod point;
+pub mod rng;
+pub mod search;
+pub mod solution;
+pub mod visual;
+
+pub use grid::{Bounds, Grid};
+pub use parse::{Cursor, ParseError};
+pub use point::{Point, Point3};
+pub use rng::Rng;
+pub use solution::Solution;
+pub use visual::Recorder;
@@ -0,0 +1,35 @@
+//! Progress and debug messages. They go to stderr, and only when asked for
+//! with `-v` or `-vv`, so stdout holds nothing but the answers.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+static VERBOSITY: AtomicU8 = AtomicU8::new(0);
+
+/// 0 prints nothing, 1 prints progress and 2 debug messages as well.
+pub fn set_verbosity(level: u8) {
+    VERBOSITY.store(level, Ordering::Relaxed);
+}
+
+pub fn verbosity() -> u8 {
+    VERBOSITY.load(Ordering::Relaxed)
+}
+
+/// Like `eprintln!`, for how far along a slow day is. Shown from `-v` on.
+#[macro_export]
+macro_rules! progress {
+    ($($arg:tt)*) => {
+        if $crate::log::verbosity() >= 1 {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+/// Like `eprintln!`, for the details of what a day is doing. Shown with `-vv`.
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if $crate::log::verbosity() >= 2 {
+            eprintln!($($arg)*);
+        }
+    };
+}
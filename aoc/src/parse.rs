@@ -0,0 +1,283 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Where and why the input could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Line of the input, counting from 1
+    pub line: usize,
+    /// Column in characters, counting from 1
+    pub column: usize,
+    /// What the parser was looking for, e.g. `a number` or `"move"`
+    pub expected: String,
+    /// What was there instead
+    pub found: String,
+}
+
+pub type Result<T> = std::result::Result<T, ParseError>;
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, expected: impl Into<String>, found: impl Into<String>) -> ParseError {
+        ParseError {
+            line,
+            column,
+            expected: expected.into(),
+            found: found.into(),
+        }
+    }
+
+    /// An error for input that stops before the parser got what it needed.
+    pub fn end_of_input(input: &str, expected: impl Into<String>) -> ParseError {
+        ParseError::new(input.lines().count() + 1, 1, expected, "end of input")
+    }
+
+    /// Renders the error together with the offending line and a marker under
+    /// the column. `source` names the input, usually its path.
+    pub fn report(&self, source: &str, input: &str) -> String {
+        let mut report = format!(
+            "error: expected {}, found {}\n --> {}:{}:{}\n",
+            self.expected, self.found, source, self.line, self.column
+        );
+
+        if let Some(text) = input.lines().nth(self.line - 1) {
+            let number = self.line.to_string();
+            let margin = " ".repeat(number.len());
+            report += &format!("{} |\n{} | {}\n", margin, number, text);
+            report += &format!("{} | {}^\n", margin, " ".repeat(self.column - 1));
+        }
+
+        report
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found {}",
+            self.line, self.column, self.expected, self.found
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Reads a single line of input from left to right, keeping track of the
+/// position for error messages.
+#[derive(Debug, Clone)]
+pub struct Cursor<'a> {
+    line: usize,
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    /// A cursor at the start of `text`, which is line `line` of the input.
+    pub fn new(line: usize, text: &'a str) -> Cursor<'a> {
+        Cursor { line, text, pos: 0 }
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.text[..self.pos].chars().count() + 1
+    }
+
+    /// The part of the line that hasn't been read yet
+    pub fn rest(&self) -> &'a str {
+        &self.text[self.pos..]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rest().is_empty()
+    }
+
+    pub fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    /// An error at the current position; the token found there is filled in.
+    pub fn error(&self, expected: impl Into<String>) -> ParseError {
+        let found = match self.rest().split_whitespace().next() {
+            _ if self.is_empty() => "end of line".to_string(),
+            Some(token) if !self.rest().starts_with(char::is_whitespace) => format!("\"{}\"", token),
+            _ => format!("{:?}", self.peek().unwrap()),
+        };
+        ParseError::new(self.line, self.column(), expected, found)
+    }
+
+    fn advance(&mut self, len: usize) -> &'a str {
+        let taken = &self.text[self.pos..self.pos + len];
+        self.pos += len;
+        taken
+    }
+
+    /// Reads any character.
+    pub fn next_char(&mut self) -> Result<char> {
+        let c = self.peek().ok_or_else(|| self.error("a character"))?;
+        self.advance(c.len_utf8());
+        Ok(c)
+    }
+
+    /// Reads one of the characters in `options`.
+    pub fn one_of(&mut self, options: &str) -> Result<char> {
+        match self.peek() {
+            Some(c) if options.contains(c) => {
+                self.advance(c.len_utf8());
+                Ok(c)
+            }
+            _ => {
+                let options: Vec<String> = options.chars().map(|c| format!("{:?}", c)).collect();
+                Err(self.error(format!("one of {}", options.join(", "))))
+            }
+        }
+    }
+
+    /// Reads exactly `tag`.
+    pub fn tag(&mut self, tag: &str) -> Result<()> {
+        if self.rest().starts_with(tag) {
+            self.advance(tag.len());
+            Ok(())
+        } else {
+            Err(self.error(format!("{:?}", tag)))
+        }
+    }
+
+    /// Reads everything up to the end of the line.
+    pub fn take_rest(&mut self) -> &'a str {
+        self.advance(self.rest().len())
+    }
+
+    /// Skips any spaces and tabs.
+    pub fn skip_whitespace(&mut self) {
+        let len = self.rest().len() - self.rest().trim_start().len();
+        self.advance(len);
+    }
+
+    /// Reads a run of letters and digits.
+    pub fn word(&mut self) -> Result<&'a str> {
+        let len = self
+            .rest()
+            .find(|c: char| !c.is_alphanumeric())
+            .unwrap_or(self.rest().len());
+        if len == 0 {
+            return Err(self.error("a word"));
+        }
+        Ok(self.advance(len))
+    }
+
+    /// Reads an integer with an optional minus sign.
+    pub fn number<T: FromStr>(&mut self) -> Result<T> {
+        let rest = self.rest();
+        let sign = if rest.starts_with('-') { 1 } else { 0 };
+        let len = rest[sign..]
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len() - sign);
+
+        match rest[..sign + len].parse() {
+            Ok(value) if len > 0 => {
+                self.advance(sign + len);
+                Ok(value)
+            }
+            _ => Err(self.error("a number")),
+        }
+    }
+
+    /// Succeeds when the whole line has been read.
+    pub fn end(&self) -> Result<()> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self.error("end of line"))
+        }
+    }
+}
+
+/// Cursors over every line of `input`, numbered from 1.
+pub fn cursors<'a>(input: &'a str) -> impl Iterator<Item = Cursor<'a>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| Cursor::new(i + 1, line))
+}
+
+/// Parses every line of `input` with `parse`, which has to read the whole line.
+pub fn lines<'a, T>(
+    input: &'a str,
+    parse: impl FnMut(&mut Cursor<'a>) -> Result<T>,
+) -> Result<Vec<T>> {
+    each(cursors(input), parse)
+}
+
+/// Like [`lines`], for a part of the input such as the lines after a header.
+pub fn each<'a, T>(
+    cursors: impl IntoIterator<Item = Cursor<'a>>,
+    mut parse: impl FnMut(&mut Cursor<'a>) -> Result<T>,
+) -> Result<Vec<T>> {
+    cursors
+        .into_iter()
+        .map(|mut cursor| {
+            let value = parse(&mut cursor)?;
+            cursor.end()?;
+            Ok(value)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor() {
+        let mut cursor = Cursor::new(1, "move 12 from -3");
+        cursor.tag("move ").unwrap();
+        assert_eq!(cursor.number::<u32>(), Ok(12));
+        cursor.skip_whitespace();
+        assert_eq!(cursor.word(), Ok("from"));
+        cursor.skip_whitespace();
+        assert_eq!(cursor.number::<i32>(), Ok(-3));
+        assert_eq!(cursor.end(), Ok(()));
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut cursor = Cursor::new(3, "2-x,4-6");
+        assert_eq!(cursor.number::<i32>(), Ok(2));
+        cursor.tag("-").unwrap();
+        assert_eq!(
+            cursor.number::<i32>(),
+            Err(ParseError::new(3, 3, "a number", "\"x,4-6\""))
+        );
+        assert_eq!(
+            cursor.one_of("ab"),
+            Err(ParseError::new(3, 3, "one of 'a', 'b'", "\"x,4-6\""))
+        );
+
+        let mut cursor = Cursor::new(1, "ab cd");
+        cursor.word().unwrap();
+        assert_eq!(cursor.end(), Err(ParseError::new(1, 3, "end of line", "' '")));
+        cursor.tag(" cd").unwrap();
+        assert_eq!(cursor.tag("x"), Err(ParseError::new(1, 6, "\"x\"", "end of line")));
+    }
+
+    #[test]
+    fn test_lines() {
+        assert_eq!(lines("1\n2\n", |c| c.number::<i32>()), Ok(vec![1, 2]));
+        assert_eq!(
+            lines("1\n2x\n", |c| c.number::<i32>()),
+            Err(ParseError::new(2, 2, "end of line", "\"x\""))
+        );
+    }
+
+    #[test]
+    fn test_report() {
+        let error = ParseError::new(2, 3, "a number", "\"x\"");
+        assert_eq!(
+            error.report("d4/input", "1-2,3-4\n2-x,4-6\n"),
+            "error: expected a number, found \"x\"\n --> d4/input:2:3\n  |\n2 | 2-x,4-6\n  |   ^\n"
+        );
+    }
+}
@@ -0,0 +1,118 @@
+use std::ops::{Add, Sub};
+
+/// A position on a 2-D map. Rows grow downwards and columns grow to the right,
+/// the way the puzzle inputs are written.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub row: i32,
+    pub col: i32,
+}
+
+impl Point {
+    pub fn new(row: i32, col: i32) -> Point {
+        Point { row, col }
+    }
+
+    pub fn advanced(&self, drow: i32, dcol: i32) -> Point {
+        Point {
+            row: self.row + drow,
+            col: self.col + dcol,
+        }
+    }
+
+    pub fn manhattan(&self, other: &Point) -> i32 {
+        (self.row - other.row).abs() + (self.col - other.col).abs()
+    }
+
+    // up, down, left, right
+    pub fn neighbors4(&self) -> [Point; 4] {
+        [
+            self.advanced(-1, 0),
+            self.advanced(1, 0),
+            self.advanced(0, -1),
+            self.advanced(0, 1),
+        ]
+    }
+
+    pub fn neighbors8(&self) -> [Point; 8] {
+        [
+            self.advanced(-1, -1),
+            self.advanced(-1, 0),
+            self.advanced(-1, 1),
+            self.advanced(0, -1),
+            self.advanced(0, 1),
+            self.advanced(1, -1),
+            self.advanced(1, 0),
+            self.advanced(1, 1),
+        ]
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        self.advanced(other.row, other.col)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        self.advanced(-other.row, -other.col)
+    }
+}
+
+/// A unit cube in 3-D space.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3 {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl Point3 {
+    pub fn new(x: i32, y: i32, z: i32) -> Point3 {
+        Point3 { x, y, z }
+    }
+
+    // Points that share a face with this one
+    pub fn neighbors(&self) -> [Point3; 6] {
+        [
+            Point3::new(self.x - 1, self.y, self.z),
+            Point3::new(self.x + 1, self.y, self.z),
+            Point3::new(self.x, self.y - 1, self.z),
+            Point3::new(self.x, self.y + 1, self.z),
+            Point3::new(self.x, self.y, self.z - 1),
+            Point3::new(self.x, self.y, self.z + 1),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_point_ops() {
+        let a = Point::new(1, 2);
+        let b = Point::new(-3, 5);
+        assert_eq!(a + b, Point::new(-2, 7));
+        assert_eq!(a - b, Point::new(4, -3));
+        assert_eq!(a.manhattan(&b), 7);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let p = Point::new(0, 0);
+        assert!(p.neighbors4().iter().all(|n| n.manhattan(&p) == 1));
+        assert!(p.neighbors8().iter().all(|n| *n != p));
+
+        let c = Point3::new(1, 1, 1);
+        for n in c.neighbors() {
+            let d = (n.x - c.x).abs() + (n.y - c.y).abs() + (n.z - c.z).abs();
+            assert_eq!(d, 1);
+        }
+    }
+}
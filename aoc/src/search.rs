@@ -0,0 +1,260 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// What a search found: the cost of every state it reached, the state each
+/// was reached from and the first goal it got to.
+#[derive(Debug, Clone)]
+pub struct Search<S, C> {
+    costs: HashMap<S, C>,
+    parents: HashMap<S, S>,
+    goal: Option<S>,
+}
+
+impl<S: Clone + Eq + Hash, C: Copy> Search<S, C> {
+    /// The goal the search stopped at, if it reached one.
+    pub fn goal(&self) -> Option<&S> {
+        self.goal.as_ref()
+    }
+
+    /// The cost of the cheapest way to the goal.
+    pub fn goal_cost(&self) -> Option<C> {
+        self.goal.as_ref().and_then(|goal| self.cost(goal))
+    }
+
+    /// The states from a start to the goal, both included.
+    pub fn goal_path(&self) -> Option<Vec<S>> {
+        self.goal.as_ref().and_then(|goal| self.path(goal))
+    }
+
+    /// The cost of the cheapest way to `state` found.
+    pub fn cost(&self, state: &S) -> Option<C> {
+        self.costs.get(state).copied()
+    }
+
+    pub fn contains(&self, state: &S) -> bool {
+        self.costs.contains_key(state)
+    }
+
+    /// Every state the search reached.
+    pub fn reached(&self) -> impl Iterator<Item = &S> {
+        self.costs.keys()
+    }
+
+    /// The states from a start to `state`, both included.
+    pub fn path(&self, state: &S) -> Option<Vec<S>> {
+        if !self.contains(state) {
+            return None;
+        }
+
+        let mut path = vec![state.clone()];
+        while let Some(parent) = self.parents.get(path.last().unwrap()) {
+            path.push(parent.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Breadth-first search, every step costs 1. It stops at the first state
+/// `is_goal` accepts; one that accepts nothing explores everything reachable.
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut search = Search {
+        costs: HashMap::new(),
+        parents: HashMap::new(),
+        goal: None,
+    };
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if !search.costs.contains_key(&start) {
+            search.costs.insert(start.clone(), 0);
+            queue.push_back(start);
+        }
+    }
+
+    while let Some(state) = queue.pop_front() {
+        if is_goal(&state) {
+            search.goal = Some(state);
+            break;
+        }
+
+        let next_cost = search.costs[&state] + 1;
+        for next in successors(&state) {
+            if search.costs.contains_key(&next) {
+                continue;
+            }
+            search.costs.insert(next.clone(), next_cost);
+            search.parents.insert(next.clone(), state.clone());
+            queue.push_back(next);
+        }
+    }
+
+    search
+}
+
+/// Cheapest-first search, `successors` gives every next state together with
+/// the cost of the step there.
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(starts, successors, |_| C::default(), is_goal)
+}
+
+/// Like [`dijkstra`], guided by a `heuristic` that estimates the cost from a
+/// state to the nearest goal. It must never overestimate, or the result might
+/// not be the cheapest.
+pub fn astar<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut search = Search {
+        costs: HashMap::new(),
+        parents: HashMap::new(),
+        goal: None,
+    };
+
+    // The heap holds indices into `pending`, so states don't need to be Ord
+    let mut pending: Vec<(S, C)> = Vec::new();
+    let mut heap = BinaryHeap::new();
+
+    for start in starts {
+        if !search.costs.contains_key(&start) {
+            search.costs.insert(start.clone(), C::default());
+            heap.push(Reverse((heuristic(&start), pending.len())));
+            pending.push((start, C::default()));
+        }
+    }
+
+    while let Some(Reverse((_, index))) = heap.pop() {
+        let (state, cost) = pending[index].clone();
+        // A cheaper way here was found after this entry was pushed
+        if search.costs[&state] < cost {
+            continue;
+        }
+
+        if is_goal(&state) {
+            search.goal = Some(state);
+            break;
+        }
+
+        for (next, step) in successors(&state) {
+            let next_cost = cost + step;
+            if search.costs.get(&next).is_some_and(|&known| known <= next_cost) {
+                continue;
+            }
+            search.costs.insert(next.clone(), next_cost);
+            search.parents.insert(next.clone(), state.clone());
+            heap.push(Reverse((next_cost + heuristic(&next), pending.len())));
+            pending.push((next, next_cost));
+        }
+    }
+
+    search
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Bounds, Grid, Point};
+
+    const MAZE: &str = "\
+..#....
+.##.##.
+...#...
+.#...#.
+";
+
+    fn maze() -> Grid<bool> {
+        Grid::parse(MAZE, "a cell", |c| Some(c == '#')).unwrap()
+    }
+
+    #[test]
+    fn test_bfs() {
+        let maze = maze();
+        let start = Point::new(0, 0);
+        let end = Point::new(0, 6);
+        let successors = |p: &Point| {
+            maze.neighbors4(*p, Bounds::Clip)
+                .filter(|n| !maze[*n])
+                .collect::<Vec<_>>()
+        };
+
+        let search = bfs([start], successors, |p| *p == end);
+        assert_eq!(search.goal_cost(), Some(12));
+
+        let path = search.goal_path().unwrap();
+        assert_eq!(path.len(), 13);
+        assert_eq!((path[0], path[12]), (start, end));
+        assert!(path.windows(2).all(|w| w[0].manhattan(&w[1]) == 1));
+        assert!(path.iter().all(|p| !maze[*p]));
+
+        // Without a goal it visits everything it can reach
+        let search = bfs([start], successors, |_| false);
+        assert_eq!(search.goal(), None);
+        assert_eq!(search.reached().count(), 20);
+        assert_eq!(search.path(&Point::new(1, 1)), None);
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        // Going straight from 0 to 3 is more expensive than the detour
+        let edges = HashMap::from([
+            (0, vec![(1, 1), (3, 10)]),
+            (1, vec![(2, 2)]),
+            (2, vec![(3, 3)]),
+            (3, vec![]),
+        ]);
+        let search = dijkstra([0], |n| edges[n].clone(), |n| *n == 3);
+        assert_eq!(search.goal_cost(), Some(6));
+        assert_eq!(search.goal_path(), Some(vec![0, 1, 2, 3]));
+    }
+
+    #[test]
+    fn test_astar() {
+        let maze = maze();
+        let end = Point::new(3, 6);
+        let search = astar(
+            [Point::new(0, 0)],
+            |p| {
+                maze.neighbors4(*p, Bounds::Clip)
+                    .filter(|n| !maze[*n])
+                    .map(|n| (n, 1))
+                    .collect::<Vec<_>>()
+            },
+            |p| p.manhattan(&end),
+            |p| *p == end,
+        );
+
+        let expected = bfs(
+            [Point::new(0, 0)],
+            |p| maze.neighbors4(*p, Bounds::Clip).filter(|n| !maze[*n]).collect::<Vec<_>>(),
+            |p| *p == end,
+        );
+        assert_eq!(search.goal_cost(), expected.goal_cost().map(|c| c as i32));
+        assert_eq!(search.goal_path().unwrap().len() as i32, search.goal_cost().unwrap() + 1);
+    }
+}
@@ -0,0 +1,26 @@
+use crate::parse::ParseError;
+use crate::rng::Rng;
+use crate::visual::Recorder;
+
+/// A day's puzzle. Both parts take the raw puzzle input and return the answer
+/// in the form the puzzle asks for it, or where the input failed to parse.
+pub trait Solution {
+    fn part1(&self, input: &str) -> Result<String, ParseError>;
+    fn part2(&self, input: &str) -> Result<String, ParseError>;
+
+    /// How many parts the puzzle has. The last day only has the one.
+    fn parts(&self) -> u8 {
+        2
+    }
+
+    /// Makes up a puzzle input, the same one for the same state of `rng`.
+    /// `size` is how big, in whatever unit suits the day: elves, lines,
+    /// sensors or the side of a map.
+    fn generate(&self, rng: &mut Rng, size: usize) -> String;
+
+    /// Runs the day's simulation, pushing frames of it into `recorder`. Only
+    /// days with something to watch record anything.
+    fn record(&self, _input: &str, _recorder: &mut Recorder) -> Result<(), ParseError> {
+        Ok(())
+    }
+}
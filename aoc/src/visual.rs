@@ -0,0 +1,246 @@
+use crate::cli::json_string;
+use crate::Grid;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+/// One picture of a simulation, a character per cell.
+pub type Frame = Grid<char>;
+
+/// Turns printed text into a frame, padding short lines with spaces.
+pub fn text_frame(text: &str) -> Frame {
+    let width = text.lines().map(|line| line.chars().count()).max().unwrap_or(0);
+    Grid::from_rows(
+        text.lines()
+            .map(|line| {
+                let mut row: Vec<char> = line.chars().collect();
+                row.resize(width, ' ');
+                row
+            })
+            .collect(),
+    )
+}
+
+/// Collects the frames a simulation pushes, to be played in the terminal or
+/// written out as an asciicast or as images.
+#[derive(Debug, Clone)]
+pub struct Recorder {
+    frames: Vec<Frame>,
+    recording: bool,
+    every: usize,
+    captured: usize,
+}
+
+impl Default for Recorder {
+    fn default() -> Recorder {
+        Recorder::new()
+    }
+}
+
+impl Recorder {
+    /// A recorder that keeps every frame.
+    pub fn new() -> Recorder {
+        Recorder {
+            frames: Vec::new(),
+            recording: true,
+            every: 1,
+            captured: 0,
+        }
+    }
+
+    /// A recorder that throws everything away without rendering it, for
+    /// running the simulations just for their answers.
+    pub fn off() -> Recorder {
+        Recorder {
+            recording: false,
+            ..Recorder::new()
+        }
+    }
+
+    /// Only keeps every `n`th frame, for simulations with a lot of steps.
+    pub fn every(self, n: usize) -> Recorder {
+        Recorder {
+            every: n.max(1),
+            ..self
+        }
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording
+    }
+
+    /// Adds a frame. `render` is only called for frames that are kept.
+    pub fn capture(&mut self, render: impl FnOnce() -> Frame) {
+        if self.recording && self.captured.is_multiple_of(self.every) {
+            self.frames.push(render());
+        }
+        self.captured += 1;
+    }
+
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+
+    /// Writes an asciicast v2 recording, which `asciinema play` replays.
+    pub fn write_asciicast(&self, mut out: impl Write, fps: f64) -> io::Result<()> {
+        let width = self.frames.iter().map(|f| f.width()).max().unwrap_or(0);
+        let height = self.frames.iter().map(|f| f.height()).max().unwrap_or(0);
+        writeln!(out, r#"{{"version": 2, "width": {}, "height": {}}}"#, width, height)?;
+
+        for (i, frame) in self.frames.iter().enumerate() {
+            let screen = format!("\x1b[H\x1b[2J{}", frame.render(|c| *c).replace('\n', "\r\n"));
+            writeln!(out, "[{:.3}, \"o\", {}]", i as f64 / fps, json_string(&screen))?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes every frame as a PPM image into `dir`, coloured by character,
+    /// with `scale` pixels to a side of a cell.
+    pub fn write_ppm(&self, dir: &Path, scale: usize) -> io::Result<()> {
+        self.write_images(dir, "ppm", |frame| {
+            let mut image = format!("P6\n{} {}\n255\n", frame.width() * scale, frame.height() * scale).into_bytes();
+            for row in frame.rows() {
+                let line: Vec<u8> = row
+                    .iter()
+                    .flat_map(|c| color(*c).repeat(scale))
+                    .collect();
+                for _ in 0..scale {
+                    image.extend(&line);
+                }
+            }
+            image
+        })
+    }
+
+    /// Writes every frame as a black and white PBM image into `dir`, with
+    /// `scale` pixels to a side of a cell. Everything but `.` and spaces is black.
+    pub fn write_pbm(&self, dir: &Path, scale: usize) -> io::Result<()> {
+        self.write_images(dir, "pbm", |frame| {
+            let width = frame.width() * scale;
+            let mut image = format!("P4\n{} {}\n", width, frame.height() * scale).into_bytes();
+            for row in frame.rows() {
+                let pixels: Vec<bool> = row
+                    .iter()
+                    .flat_map(|c| std::iter::repeat_n(!is_background(*c), scale))
+                    .collect();
+                // Every row starts on a new byte, 8 pixels to a byte
+                let line: Vec<u8> = pixels
+                    .chunks(8)
+                    .map(|bits| bits.iter().enumerate().fold(0, |byte, (i, &bit)| byte | (bit as u8) << (7 - i)))
+                    .collect();
+                for _ in 0..scale {
+                    image.extend(&line);
+                }
+            }
+            image
+        })
+    }
+
+    fn write_images(&self, dir: &Path, extension: &str, encode: impl Fn(&Frame) -> Vec<u8>) -> io::Result<()> {
+        fs::create_dir_all(dir)?;
+        for (i, frame) in self.frames.iter().enumerate() {
+            fs::write(dir.join(format!("frame{:05}.{}", i, extension)), encode(frame))?;
+        }
+        Ok(())
+    }
+
+    /// Shows the frames one after another in the terminal.
+    pub fn play(&self, mut out: impl Write, fps: f64) -> io::Result<()> {
+        let delay = Duration::from_secs_f64(1.0 / fps);
+        for frame in &self.frames {
+            write!(out, "\x1b[H\x1b[2J{}", frame.render(|c| *c))?;
+            out.flush()?;
+            thread::sleep(delay);
+        }
+        Ok(())
+    }
+}
+
+fn is_background(c: char) -> bool {
+    c == '.' || c == ' '
+}
+
+// Walls and rock are white, everything else gets a colour of its own
+fn color(c: char) -> [u8; 3] {
+    const PALETTE: [[u8; 3]; 6] = [
+        [230, 80, 60],
+        [80, 190, 90],
+        [70, 130, 230],
+        [240, 200, 60],
+        [190, 90, 210],
+        [60, 200, 210],
+    ];
+
+    match c {
+        _ if is_background(c) => [0, 0, 0],
+        '#' => [255, 255, 255],
+        _ => PALETTE[c as usize % PALETTE.len()],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recorded() -> Recorder {
+        let mut recorder = Recorder::new();
+        recorder.capture(|| text_frame("#.\n.#\n"));
+        recorder.capture(|| text_frame("o\n\"#\n"));
+        recorder
+    }
+
+    #[test]
+    fn test_capture() {
+        let recorder = recorded();
+        assert_eq!(recorder.frames().len(), 2);
+        assert_eq!(recorder.frames()[1].render(|c| *c), "o \n\"#\n");
+
+        let mut recorder = Recorder::new().every(3);
+        for _ in 0..7 {
+            recorder.capture(|| text_frame("x"));
+        }
+        assert_eq!(recorder.frames().len(), 3);
+
+        let mut recorder = Recorder::off();
+        recorder.capture(|| panic!("Rendered a frame while not recording"));
+        assert!(recorder.frames().is_empty());
+    }
+
+    #[test]
+    fn test_asciicast() {
+        let mut out = Vec::new();
+        recorded().write_asciicast(&mut out, 2.0).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            concat!(
+                "{\"version\": 2, \"width\": 2, \"height\": 2}\n",
+                "[0.000, \"o\", \"\\u001b[H\\u001b[2J#.\\r\\n.#\\r\\n\"]\n",
+                "[0.500, \"o\", \"\\u001b[H\\u001b[2Jo \\r\\n\\\"#\\r\\n\"]\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_images() {
+        let dir = std::env::temp_dir().join(format!("aoc-visual-{}", std::process::id()));
+        let recorder = recorded();
+
+        recorder.write_pbm(&dir, 1).unwrap();
+        let pbm = fs::read(dir.join("frame00000.pbm")).unwrap();
+        assert_eq!(pbm, b"P4\n2 2\n\x80\x40");
+
+        recorder.write_ppm(&dir, 2).unwrap();
+        let ppm = fs::read(dir.join("frame00001.ppm")).unwrap();
+        let header = b"P6\n4 4\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+        assert_eq!(ppm.len(), header.len() + 4 * 4 * 3);
+        // The top left cell is 'o', the bottom right '#'
+        assert_eq!(ppm[header.len()..header.len() + 3], color('o'));
+        assert_eq!(ppm[ppm.len() - 3..], [255, 255, 255]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,14 @@
+use aoc::Rng;
+
+/// `size` elves, each carrying a handful of snacks.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let elves: Vec<String> = (0..size.max(1))
+        .map(|_| {
+            let snacks = rng.between(1, 15);
+            (0..snacks)
+                .map(|_| format!("{}\n", rng.between(1000, 60000)))
+                .collect()
+        })
+        .collect();
+    elves.join("\n")
+}
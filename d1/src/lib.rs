@@ -0,0 +1,335 @@
+//! Day 1: Calorie Counting. The elves' snacks are summed up per elf.
+
+use aoc::{Cursor, ParseError, Rng, Solution};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt;
+use std::io::{self, BufRead};
+
+mod generate;
+
+/// Why the elves couldn't be read.
+#[derive(Debug)]
+pub enum ReadError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl From<io::Error> for ReadError {
+    fn from(err: io::Error) -> ReadError {
+        ReadError::Io(err)
+    }
+}
+
+impl From<ParseError> for ReadError {
+    fn from(err: ParseError) -> ReadError {
+        ReadError::Parse(err)
+    }
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReadError::Io(err) => write!(f, "{}", err),
+            ReadError::Parse(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for ReadError {}
+
+/// The total calories of every elf, read line by line. Only the line being
+/// read is kept in memory, so the inventory can be as large as it likes.
+///
+/// Elves are separated by one or more blank lines. Line endings may be `\n`
+/// or `\r\n`, and whitespace at the end of a line is ignored. A line that
+/// isn't a number is an error with its line number.
+pub struct Elves<R> {
+    reader: R,
+    line: String,
+    line_number: usize,
+}
+
+/// The elves of `reader`, see `Elves`.
+pub fn read_elves<R: BufRead>(reader: R) -> Elves<R> {
+    Elves {
+        reader,
+        line: String::new(),
+        line_number: 0,
+    }
+}
+
+impl<R: BufRead> Iterator for Elves<R> {
+    type Item = Result<i32, ReadError>;
+
+    fn next(&mut self) -> Option<Result<i32, ReadError>> {
+        let mut total: Option<i32> = None;
+        loop {
+            self.line.clear();
+            match self.reader.read_line(&mut self.line) {
+                Ok(0) => return total.map(Ok),
+                Ok(_) => self.line_number += 1,
+                Err(err) => return Some(Err(err.into())),
+            }
+
+            let text = self.line.trim_end();
+            if text.is_empty() {
+                match total {
+                    Some(total) => return Some(Ok(total)),
+                    None => continue,
+                }
+            }
+
+            let mut cursor = Cursor::new(self.line_number, text);
+            let column = cursor.column();
+            let calories = match cursor.number::<i32>().and_then(|calories| cursor.end().map(|_| calories)) {
+                Ok(calories) => calories,
+                Err(err) => return Some(Err(err.into())),
+            };
+            match total.unwrap_or(0).checked_add(calories) {
+                Some(sum) => total = Some(sum),
+                None => {
+                    let err = ParseError::new(self.line_number, column, "a total that fits in 32 bits", format!("{:?}", text));
+                    return Some(Err(err.into()));
+                }
+            }
+        }
+    }
+}
+
+// Reading from memory can only fail to parse
+fn in_memory(err: ReadError) -> ParseError {
+    match err {
+        ReadError::Parse(err) => err,
+        ReadError::Io(err) => unreachable!("Reading from a string failed: {}", err),
+    }
+}
+
+/// Total calories per elf, see `Elves` for the format.
+pub fn parse_elves(input: &str) -> Result<Vec<i32>, ParseError> {
+    read_elves(input.as_bytes()).collect::<Result<_, _>>().map_err(in_memory)
+}
+
+/// The `count` best stocked elves as (index, total), most calories first.
+/// Elves with the same total keep their order. Only `count` elves are kept
+/// at any time, the rest is never sorted.
+pub fn top_elves(elves: impl IntoIterator<Item = i32>, count: usize) -> Vec<(usize, i32)> {
+    // A min-heap of the best so far, so the weakest of them is at the top
+    let mut heap = BinaryHeap::with_capacity(count + 1);
+    for (index, total) in elves.into_iter().enumerate() {
+        heap.push(Reverse((total, Reverse(index))));
+        if heap.len() > count {
+            heap.pop();
+        }
+    }
+
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|Reverse((total, Reverse(index)))| (index, total))
+        .collect()
+}
+
+/// Sum of the calories carried by the `count` best stocked elves.
+pub fn top_total(input: &str, count: usize) -> Result<i32, ParseError> {
+    let elves = parse_elves(input)?;
+
+    Ok(top_elves(elves, count).iter().map(|(_, total)| total).sum())
+}
+
+/// Summary of the calories the elves carry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub count: usize,
+    pub min: i32,
+    pub max: i32,
+    pub mean: f64,
+    pub median: f64,
+}
+
+impl Stats {
+    /// The statistics of the totals, None if there are no elves.
+    pub fn of(elves: &[i32]) -> Option<Stats> {
+        let mut tally = Tally::default();
+        for &total in elves {
+            tally.add(total);
+        }
+        tally.stats()
+    }
+}
+
+// Stats kept up to date elf by elf. All but the median are running values;
+// the median needs every total, so those are the one thing that grows with
+// the number of elves.
+#[derive(Default)]
+struct Tally {
+    min: i32,
+    max: i32,
+    sum: i64,
+    totals: Vec<i32>,
+}
+
+impl Tally {
+    fn add(&mut self, total: i32) {
+        if self.totals.is_empty() {
+            (self.min, self.max) = (total, total);
+        }
+        self.min = self.min.min(total);
+        self.max = self.max.max(total);
+        self.sum += total as i64;
+        self.totals.push(total);
+    }
+
+    fn stats(mut self) -> Option<Stats> {
+        let count = self.totals.len();
+        if count == 0 {
+            return None;
+        }
+
+        let (below, &mut middle, _) = self.totals.select_nth_unstable(count / 2);
+        let median = if count % 2 == 1 {
+            middle as f64
+        } else {
+            (*below.iter().max().unwrap() as f64 + middle as f64) / 2.0
+        };
+
+        Some(Stats {
+            count,
+            min: self.min,
+            max: self.max,
+            mean: self.sum as f64 / count as f64,
+            median,
+        })
+    }
+}
+
+/// The best stocked elves and the statistics of all of them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    /// (index, total) of the top elves, most calories first
+    pub top: Vec<(usize, i32)>,
+    pub stats: Stats,
+}
+
+impl Report {
+    /// The report on the `count` best stocked elves of the input.
+    pub fn new(input: &str, count: usize) -> Result<Report, ParseError> {
+        Report::read(input.as_bytes(), count).map_err(in_memory)
+    }
+
+    /// The report on the `count` best stocked elves, read line by line. The
+    /// top elves and the statistics are kept up to date as the elves come,
+    /// except for the median, which needs the total of every elf. Their
+    /// snacks are never kept.
+    pub fn read(reader: impl BufRead, count: usize) -> Result<Report, ReadError> {
+        let mut tally = Tally::default();
+        let mut failure = None;
+        let elves = read_elves(reader)
+            .map_while(|elf| elf.map_err(|err| failure = Some(err)).ok())
+            .inspect(|&total| tally.add(total));
+        let top = top_elves(elves, count);
+        if let Some(err) = failure {
+            return Err(err);
+        }
+
+        let stats = tally.stats().ok_or_else(|| ParseError::end_of_input("", "the calories of an elf"))?;
+        Ok(Report { top, stats })
+    }
+
+    /// Calories carried by the top elves together.
+    pub fn total(&self) -> i32 {
+        self.top.iter().map(|(_, total)| total).sum()
+    }
+}
+
+// Elves are numbered from 1 here, the way they are counted in the input
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Top {} of {} elves:", self.top.len(), self.stats.count)?;
+        for (rank, (index, total)) in self.top.iter().enumerate() {
+            writeln!(f, "{:4}. elf {}: {}", rank + 1, index + 1, total)?;
+        }
+        writeln!(f, "Together: {}", self.total())?;
+        writeln!(
+            f,
+            "Min {}, max {}, mean {:.1}, median {:.1}",
+            self.stats.min, self.stats.max, self.stats.mean, self.stats.median
+        )
+    }
+}
+
+/// Part 1 is the most calories one elf carries, part 2 the total of the top three.
+pub struct Day1;
+
+impl Solution for Day1 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        Ok(top_total(input, 1)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        Ok(top_total(input, 3)?.to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate::generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_top_elves() {
+        let elves = [6000, 4000, 11000, 24000, 10000];
+        assert_eq!(top_elves(elves, 1), vec![(3, 24000)]);
+        assert_eq!(top_elves(elves, 3), vec![(3, 24000), (2, 11000), (4, 10000)]);
+        assert_eq!(top_elves(elves, 9).len(), 5);
+        assert_eq!(top_elves(elves, 0), vec![]);
+
+        // Ties keep the order of the input
+        assert_eq!(top_elves([5, 7, 5, 5], 3), vec![(1, 7), (0, 5), (2, 5)]);
+    }
+
+    #[test]
+    fn test_report() {
+        let report = Report::new(include_str!("../sample.txt"), 2).unwrap();
+        assert_eq!(report.top, vec![(3, 24000), (2, 11000)]);
+        assert_eq!(report.total(), 35000);
+        assert_eq!(
+            report.stats,
+            Stats { count: 5, min: 4000, max: 24000, mean: 11000.0, median: 10000.0 }
+        );
+        assert_eq!(
+            report.to_string(),
+            "Top 2 of 5 elves:\n   1. elf 4: 24000\n   2. elf 3: 11000\nTogether: 35000\n\
+             Min 4000, max 24000, mean 11000.0, median 10000.0\n"
+        );
+
+        assert_eq!(Stats::of(&[1, 4, 2, 8]).unwrap().median, 3.0);
+        assert_eq!(Stats::of(&[]), None);
+        assert!(Report::new("\n\n", 1).is_err());
+        assert_eq!(Report::new("1\n\n2\nx\n", 1), Err(ParseError::new(4, 1, "a number", "\"x\"")));
+        assert!(matches!(Report::read(&b"1\n\n\xff\n"[..], 1), Err(ReadError::Io(_))));
+    }
+
+    #[test]
+    fn test_read_elves() {
+        let elves = |input: &str| parse_elves(input);
+
+        assert_eq!(elves("1\n2\n\n3\n"), Ok(vec![3, 3]));
+        assert_eq!(elves("1\r\n2\r\n\r\n3"), Ok(vec![3, 3]));
+        assert_eq!(elves("\n1\n\n\n\n2  \n \t\n3\n\n\n"), Ok(vec![1, 2, 3]));
+        assert_eq!(elves(""), Ok(vec![]));
+
+        assert_eq!(elves("1\n\nx2\n"), Err(ParseError::new(3, 1, "a number", "\"x2\"")));
+        assert_eq!(elves("1\r\n2 3\r\n"), Err(ParseError::new(2, 2, "end of line", "' '")));
+        assert_eq!(
+            elves("2147483647\n1\n"),
+            Err(ParseError::new(2, 1, "a total that fits in 32 bits", "\"1\""))
+        );
+
+        // Invalid UTF-8 is an I/O error, not a panic
+        let mut read = read_elves(&b"1\n\xff\n"[..]);
+        assert!(matches!(read.next(), Some(Err(ReadError::Io(_)))));
+    }
+}
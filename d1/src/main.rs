@@ -1,24 +1,42 @@
-use std::io::{self, Read};
+use aoc::cli::{self, Format, Options};
+use d1::{Day1, ReadError, Report};
 
-fn main() {
-    let mut input = String::new();
-
-    io::stdin()
-        .read_to_string(&mut input)
-        .expect("Failed to read");
+const USAGE: &str = "d1 also takes --top <K> to report on the K best stocked elves instead";
 
-    let mut elves = input
-        .split("\n\n")
-        .map(str::trim)
-        .map(|elf| {
-            elf.split('\n').map(|f| f.parse::<i32>().unwrap()).sum()
-        })
-        .collect::<Vec<i32>>();
-
-    elves.sort_by(|a, b| b.cmp(a));
+fn main() {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let top = match args.iter().position(|arg| arg == "--top") {
+        Some(i) => {
+            let count = args.get(i + 1).and_then(|count| count.parse::<usize>().ok());
+            if count.is_none() {
+                eprintln!("--top needs a number\n\n{}", USAGE);
+                std::process::exit(2);
+            }
+            args.drain(i..i + 2);
+            count
+        }
+        None => None,
+    };
 
-    // Get sum of first 3 elements
-    let result = elves.iter().take(3).sum::<i32>();
+    let options = Options::from_args(args);
+    let Some(count) = top else {
+        cli::run(1, &Day1, env!("CARGO_MANIFEST_DIR"), &options);
+        return;
+    };
 
-    println!("{}", result);
+    if options.format != Format::Text {
+        eprintln!("--top only makes a text report\n\n{}", USAGE);
+        std::process::exit(2);
+    }
+    // The inventory is read as it comes; only the elves' totals are kept,
+    // for the median
+    let (source, reader) = cli::open_input(env!("CARGO_MANIFEST_DIR"), &options);
+    match Report::read(reader, count) {
+        Ok(report) => print!("{}", report),
+        Err(ReadError::Parse(err)) => cli::fail(&err, &source, ""),
+        Err(ReadError::Io(err)) => {
+            eprintln!("Can't read {}: {}", source, err);
+            std::process::exit(1);
+        }
+    }
 }
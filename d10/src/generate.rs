@@ -0,0 +1,14 @@
+use aoc::Rng;
+
+/// A program of `size` instructions.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            if rng.chance(0.4) {
+                "noop\n".to_string()
+            } else {
+                format!("addx {}\n", rng.between(-10, 10))
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,132 @@
+//! Day 10: Cathode-Ray Tube. Runs the CPU program and draws what the CRT
+//! shows.
+
+use aoc::{Cursor, ParseError, Rng, Solution};
+
+#[cfg(test)]
+use proptest_derive::Arbitrary;
+
+mod generate;
+
+/// An instruction of the program.
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(test, derive(Arbitrary))]
+pub enum Operation {
+    NoOp,
+    AddX(i32),
+}
+
+impl Operation {
+    /// `noop` or `addx <value>`.
+    pub fn parse(cursor: &mut Cursor) -> Result<Operation, ParseError> {
+        let start = cursor.clone();
+        match cursor.word()? {
+            "noop" => Ok(Operation::NoOp),
+            "addx" => {
+                cursor.tag(" ")?;
+                Ok(Operation::AddX(cursor.number()?))
+            }
+            _ => Err(start.error("\"noop\" or \"addx\"")),
+        }
+    }
+}
+
+// implements parse for Operation
+impl std::str::FromStr for Operation {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cursor = Cursor::new(1, s);
+        let operation = Operation::parse(&mut cursor)?;
+        cursor.end()?;
+        Ok(operation)
+    }
+}
+
+/// Value of the X register during every cycle.
+pub fn cycles_to_state(input: &str) -> Result<Vec<i32>, ParseError> {
+    let mut state: i32 = 1;
+
+    let mut cycles_to_state = Vec::new();
+
+    for operation in aoc::parse::lines(input, Operation::parse)? {
+        cycles_to_state.push(state);
+        if let Operation::AddX(value) = operation {
+            cycles_to_state.push(state);
+            state += value;
+        }
+    }
+
+    Ok(cycles_to_state)
+}
+
+/// Part 1 sums the signal strengths, part 2 is the image drawn on the CRT.
+pub struct Day10;
+
+impl Solution for Day10 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        let mut total = 0;
+
+        for (i, state) in cycles_to_state(input)?.iter().enumerate().skip(19).step_by(40) {
+            aoc::debug!("{}: {}", i + 1, state);
+            total += (i as i32 + 1) * state
+        }
+
+        Ok(total.to_string())
+    }
+
+    // The answer is the image drawn on the CRT
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        let mut output = String::new();
+
+        for (i, state) in cycles_to_state(input)?.iter().enumerate() {
+            let row_position = (i % 40) as i32;
+
+            if (row_position - state).abs() <= 1 {
+                output.push('#');
+            } else {
+                output.push('.');
+            }
+
+            if row_position == 39 {
+                output.push('\n')
+            }
+        }
+
+        Ok(output.trim_end().to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate::generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn test_parse(operation: Operation) {
+            let s = match operation {
+                Operation::NoOp => "noop".to_string(),
+                Operation::AddX(value) => format!("addx {}", value),
+            };
+            assert_eq!(s.parse::<Operation>().unwrap(), operation);
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            "addy 3".parse::<Operation>(),
+            Err(ParseError::new(1, 1, "\"noop\" or \"addx\"", "\"addy\""))
+        );
+        assert_eq!(
+            "addx x".parse::<Operation>(),
+            Err(ParseError::new(1, 6, "a number", "\"x\""))
+        );
+    }
+}
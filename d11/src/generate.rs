@@ -0,0 +1,99 @@
+use aoc::Rng;
+
+const PRIMES: [i64; 10] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29];
+
+#[derive(Clone, Copy)]
+enum Operation {
+    Add(i64),
+    Multiply(i64),
+}
+
+struct Monkey {
+    items: Vec<i64>,
+    operation: Operation,
+    divisor: i64,
+    if_true: usize,
+    if_false: usize,
+}
+
+/// Notes on `size` monkeys, from 2 up to 10 so that the product of the
+/// divisors stays small. There is no `old * old`: squaring overflows part 1
+/// on most random monkeys.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let count = size.clamp(2, PRIMES.len());
+    let mut divisors = PRIMES.to_vec();
+    rng.shuffle(&mut divisors);
+
+    let mut monkeys: Vec<Monkey> = (0..count)
+        .map(|i| {
+            let mut other = || (i + 1 + rng.below(count - 1)) % count;
+            let (if_true, if_false) = (other(), other());
+            Monkey {
+                items: (0..rng.between(1, 4)).map(|_| rng.between(50, 99)).collect(),
+                operation: if rng.chance(0.5) {
+                    Operation::Add(rng.between(1, 8))
+                } else {
+                    Operation::Multiply(rng.between(2, 19))
+                },
+                divisor: divisors[i],
+                if_true,
+                if_false,
+            }
+        })
+        .collect();
+
+    // Adding always keeps the worry levels down in part 1
+    while overflows(&monkeys) {
+        let multiplying: Vec<usize> = (0..count)
+            .filter(|&i| matches!(monkeys[i].operation, Operation::Multiply(_)))
+            .collect();
+        let i = *rng.pick(&multiplying);
+        monkeys[i].operation = Operation::Add(rng.between(1, 8));
+    }
+
+    let notes: Vec<String> = monkeys
+        .iter()
+        .enumerate()
+        .map(|(i, monkey)| {
+            let items: Vec<String> = monkey.items.iter().map(|item| item.to_string()).collect();
+            let operation = match monkey.operation {
+                Operation::Add(n) => format!("old + {}", n),
+                Operation::Multiply(n) => format!("old * {}", n),
+            };
+            format!(
+                "Monkey {}:\n  Starting items: {}\n  Operation: new = {}\n  Test: divisible by {}\n    If true: throw to monkey {}\n    If false: throw to monkey {}\n",
+                i,
+                items.join(", "),
+                operation,
+                monkey.divisor,
+                monkey.if_true,
+                monkey.if_false
+            )
+        })
+        .collect();
+    notes.join("\n")
+}
+
+// Whether a worry level gets too big for an i64 in the 20 rounds of part 1
+fn overflows(monkeys: &[Monkey]) -> bool {
+    let mut items: Vec<Vec<i64>> = monkeys.iter().map(|monkey| monkey.items.clone()).collect();
+
+    for _ in 0..20 {
+        for (i, monkey) in monkeys.iter().enumerate() {
+            for item in std::mem::take(&mut items[i]) {
+                let worry = match monkey.operation {
+                    Operation::Add(n) => item.checked_add(n),
+                    Operation::Multiply(n) => item.checked_mul(n),
+                };
+                let Some(worry) = worry else {
+                    return true;
+                };
+                let worry = worry / 3;
+                let target = if worry % monkey.divisor == 0 { monkey.if_true } else { monkey.if_false };
+                items[target].push(worry);
+            }
+        }
+    }
+
+    false
+}
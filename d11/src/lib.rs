@@ -0,0 +1,316 @@
+//! Day 11: Monkey in the Middle. The monkeys throw the items around by
+//! worry level.
+
+use aoc::visual::{self, Frame};
+use aoc::{Cursor, ParseError, Recorder, Rng, Solution};
+
+mod generate;
+
+/// How a monkey changes the worry level of an item it inspects.
+pub struct Operation {
+    left: Box<dyn Fn(i64) -> i64>,
+    right: Box<dyn Fn(i64) -> i64>,
+    op: Box<dyn Fn(i64, i64) -> i64>,
+}
+
+impl Operation {
+    /// The new worry level of an item with worry level `cur`.
+    pub fn apply(&self, cur: i64) -> i64 {
+        let left = (self.left)(cur);
+        let right = (self.right)(cur);
+        (self.op)(left, right)
+    }
+}
+
+// Picks the monkey an item with the given worry level is thrown to
+type Picker = Box<dyn Fn(i64) -> usize>;
+
+/// A monkey with the worry levels of the items it holds.
+pub struct Monkey {
+    pub items: Vec<i64>,
+    operation: Operation,
+    picker: Picker,
+    /// The monkey decides where to throw an item by whether this divides it
+    pub divisor: i64,
+    /// How many items the monkey inspected so far
+    pub inspected: i64,
+}
+
+fn parse_picker(
+    test_line: &mut Cursor,
+    true_line: &mut Cursor,
+    false_line: &mut Cursor,
+) -> Result<(Picker, i64), ParseError> {
+    fn pick_last_number(cursor: &mut Cursor, prefix: &str) -> Result<i64, ParseError> {
+        cursor.skip_whitespace();
+        cursor.tag(prefix)?;
+        let number = cursor.number()?;
+        cursor.end()?;
+        Ok(number)
+    }
+
+    let start = test_line.clone();
+    let modulo = pick_last_number(test_line, "Test: divisible by ")?;
+    if modulo == 0 {
+        return Err(start.error("a divisor other than 0"));
+    }
+    let if_true = pick_last_number(true_line, "If true: throw to monkey ")? as usize;
+    let if_false = pick_last_number(false_line, "If false: throw to monkey ")? as usize;
+
+    Ok((
+        Box::new(move |x| {
+            if x % modulo == 0 {
+                if_true
+            } else {
+                if_false
+            }
+        }),
+        modulo,
+    ))
+}
+
+fn parse_items(cursor: &mut Cursor) -> Result<Vec<i64>, ParseError> {
+    let mut items = Vec::new();
+    while !cursor.is_empty() {
+        if !items.is_empty() {
+            cursor.tag(", ")?;
+        }
+        items.push(cursor.number()?);
+    }
+    Ok(items)
+}
+
+impl Operation {
+    /// `new = <operand> <op> <operand>`, where an operand is `old` or a number.
+    pub fn parse(cursor: &mut Cursor) -> Result<Operation, ParseError> {
+        fn make_picker_from_operand(cursor: &mut Cursor) -> Result<Box<dyn Fn(i64) -> i64>, ParseError> {
+            if cursor.rest().starts_with("old") {
+                cursor.tag("old")?;
+                return Ok(Box::new(|x| x));
+            }
+            let val: i64 = cursor.number().map_err(|_| cursor.error("\"old\" or a number"))?;
+            Ok(Box::new(move |_| val))
+        }
+
+        cursor.tag("new = ")?;
+        let left = make_picker_from_operand(cursor)?;
+        cursor.tag(" ")?;
+        let op = match cursor.one_of("+-*/")? {
+            '+' => |x, y| x + y,
+            '-' => |x, y| x - y,
+            '*' => |x, y| x * y,
+            _ => |x, y| x / y,
+        };
+        cursor.tag(" ")?;
+        let right = make_picker_from_operand(cursor)?;
+
+        Ok(Operation {
+            left,
+            right,
+            op: Box::new(op),
+        })
+    }
+}
+
+impl std::str::FromStr for Operation {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cursor = Cursor::new(1, s);
+        let operation = Operation::parse(&mut cursor)?;
+        cursor.end()?;
+        Ok(operation)
+    }
+}
+
+/// A monkey is described by six lines:
+///
+/// ```text
+/// Monkey 0:
+///   Starting items: 79, 98
+///   Operation: new = old * 19
+///   Test: divisible by 23
+///     If true: throw to monkey 2
+///     If false: throw to monkey 3
+/// ```
+pub fn parse_monkey(monkey_input: &mut [Cursor]) -> Result<Monkey, ParseError> {
+    if monkey_input.len() > 6 {
+        return Err(monkey_input[6].error("an empty line between monkeys"));
+    }
+    let last_line = monkey_input.last().unwrap().line();
+    let [header, items, operation, test, if_true, if_false] = monkey_input else {
+        return Err(ParseError::new(last_line + 1, 1, "the rest of the monkey", "end of monkey"));
+    };
+
+    header.tag("Monkey ")?;
+    header.number::<usize>()?;
+    header.tag(":")?;
+    header.end()?;
+
+    items.tag("  Starting items: ")?;
+    let items = parse_items(items)?;
+
+    operation.tag("  Operation: ")?;
+    let parsed_operation = Operation::parse(operation)?;
+    operation.end()?;
+
+    let (picker, divisor) = parse_picker(test, if_true, if_false)?;
+    Ok(Monkey {
+        items,
+        operation: parsed_operation,
+        picker,
+        divisor,
+        inspected: 0,
+    })
+}
+
+// The items every monkey holds after a round
+fn monkeys_frame(round: usize, monkeys: &[Monkey]) -> Frame {
+    let mut text = format!("After round {}\n", round);
+    for (i, monkey) in monkeys.iter().enumerate() {
+        text += &format!("Monkey {}: {:?}\n", i, monkey.items);
+    }
+    visual::text_frame(&text)
+}
+
+/// Plays `rounds` rounds and returns the level of monkey business. `relief`
+/// is applied to every worry level after the monkey inspects the item, and
+/// gets the product of all divisors as well. Every round is a frame for
+/// `recorder`.
+pub fn monkey_business(
+    input: &str,
+    rounds: usize,
+    relief: impl Fn(i64, i64) -> i64,
+    recorder: &mut Recorder,
+) -> Result<i64, ParseError> {
+    let lines: Vec<Cursor> = aoc::parse::cursors(input).collect();
+
+    let mut monkeys = lines
+        .split(|x| x.is_empty())
+        .map(|monkey_lines| parse_monkey(&mut monkey_lines.to_vec()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let modulo = monkeys.iter().map(|m| m.divisor).product::<i64>();
+    aoc::debug!("modulo = {}", modulo);
+
+    for round in 1..=rounds {
+        for cur_monkey_index in 0..monkeys.len() {
+            let cur_monkey = &mut monkeys[cur_monkey_index];
+
+            let new_positions = cur_monkey
+                .items
+                .iter()
+                .map(|x| cur_monkey.operation.apply(*x))
+                .map(|x| relief(x, modulo))
+                .map(|x| ((cur_monkey.picker)(x), x))
+                .collect::<Vec<_>>();
+
+            cur_monkey.inspected += cur_monkey.items.len() as i64;
+            cur_monkey.items.clear();
+
+            for (monkey_index, item) in new_positions {
+                monkeys[monkey_index].items.push(item);
+            }
+        }
+
+        recorder.capture(|| monkeys_frame(round, &monkeys));
+    }
+
+    let mut inspected = monkeys.iter().map(|x| x.inspected).collect::<Vec<_>>();
+
+    inspected.sort();
+    inspected.reverse();
+
+    Ok(inspected[0] * inspected[1])
+}
+
+/// Part 1 plays 20 rounds with relief after every inspection, part 2 10000
+/// rounds without.
+pub struct Day11;
+
+impl Solution for Day11 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        Ok(monkey_business(input, 20, |x, _| x / 3, &mut Recorder::off())?.to_string())
+    }
+
+    // Without relief worry levels grow without bound, but only their
+    // remainders modulo the product of all divisors matter
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        Ok(monkey_business(input, 10000, |x, modulo| x % modulo, &mut Recorder::off())?.to_string())
+    }
+
+    fn record(&self, input: &str, recorder: &mut Recorder) -> Result<(), ParseError> {
+        monkey_business(input, 20, |x, _| x / 3, recorder)?;
+        Ok(())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate::generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply() {
+        let op = Operation {
+            left: Box::new(|x| x + 1),
+            right: Box::new(|x| x * 2),
+            op: Box::new(|x, y| x - y),
+        };
+        assert_eq!(op.apply(1), 0);
+
+        let op = Operation {
+            left: Box::new(|x| x),
+            right: Box::new(|_| 5),
+            op: Box::new(|x, y| x + y),
+        };
+        assert_eq!(op.apply(1), 6);
+    }
+
+    #[test]
+    fn test_parse_operation() {
+        let operation = "new = old + 1".parse::<Operation>().unwrap();
+        assert_eq!(operation.apply(1), 2);
+
+        let operation = "new = old * 5".parse::<Operation>().unwrap();
+        assert_eq!(operation.apply(2), 10);
+
+        let operation = "new = old * old".parse::<Operation>().unwrap();
+        assert_eq!(operation.apply(3), 9);
+    }
+
+    #[test]
+    fn test_parse_picker() {
+        let (picker, divisor) = parse_picker(
+            &mut Cursor::new(1, "Test: divisible by 13"),
+            &mut Cursor::new(2, "If true: throw to monkey 5"),
+            &mut Cursor::new(3, "If false: throw to monkey 7"),
+        )
+        .unwrap();
+        assert_eq!(divisor, 13);
+        assert_eq!(picker(3), 7);
+        assert_eq!(picker(8), 7);
+        assert_eq!(picker(26), 5);
+    }
+
+    #[test]
+    fn test_parse_items() {
+        let items = parse_items(&mut Cursor::new(1, "1, 3, 4, 5"));
+        assert_eq!(items, Ok(vec![1, 3, 4, 5]));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = "new = old % 3".parse::<Operation>().err();
+        assert_eq!(error, Some(ParseError::new(1, 11, "one of '+', '-', '*', '/'", "\"%\"")));
+
+        let error = "new = old * x".parse::<Operation>().err();
+        assert_eq!(error, Some(ParseError::new(1, 13, "\"old\" or a number", "\"x\"")));
+
+        let items = parse_items(&mut Cursor::new(2, "1, 3 4"));
+        assert_eq!(items, Err(ParseError::new(2, 5, "\", \"", "' '")));
+    }
+}
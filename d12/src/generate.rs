@@ -0,0 +1,27 @@
+use aoc::{Grid, Point, Rng};
+
+/// A heightmap of `size` rows and twice as many columns. The land rises
+/// towards the best signal, with some noise, so there is usually a way up.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let rows = size.max(2);
+    let mut map = Grid::new(rows, 2 * rows, 'a');
+
+    let end = Point::new(rng.below(map.height()) as i32, rng.below(map.width()) as i32);
+    let furthest = map.points().map(|p| p.manhattan(&end)).max().unwrap();
+
+    for point in map.points().collect::<Vec<_>>() {
+        let height = 25 - point.manhattan(&end) * 25 / furthest + rng.between(-1, 1) as i32;
+        map[point] = (b'a' + height.clamp(0, 25) as u8) as char;
+    }
+
+    let start = loop {
+        let start = Point::new(rng.below(map.height()) as i32, rng.below(map.width()) as i32);
+        if start != end {
+            break start;
+        }
+    };
+    map[start] = 'S';
+    map[end] = 'E';
+
+    map.render(|c| *c)
+}
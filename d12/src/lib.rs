@@ -0,0 +1,88 @@
+//! Day 12: Hill Climbing Algorithm. Searches the heightmap for the shortest
+//! climb to the best signal.
+
+use aoc::search::{self, Search};
+use aoc::{Bounds, Grid, ParseError, Point, Rng, Solution};
+
+mod generate;
+
+/// Height of a square, 0 for `a` to 25 for `z`. The start `S` is at height
+/// `a`, the best signal `E` at height `z`.
+pub fn char_to_height(c: char) -> i32 {
+    match c {
+        'S' => 0,
+        'E' => 25,
+        _ => c as i32 - 'a' as i32,
+    }
+}
+
+/// Searches for the shortest climb to 'E' from any square accepted by
+/// `is_start`.
+pub fn climb(input: &str, is_start: impl Fn(char) -> bool) -> Result<Search<Point, usize>, ParseError> {
+    let field_chars = Grid::parse(input, "a height from 'a' to 'z', 'S' or 'E'", |c| {
+        Some(c).filter(|c| c.is_ascii_lowercase() || *c == 'S' || *c == 'E')
+    })?;
+
+    let field = field_chars.map(|c| char_to_height(*c));
+
+    let end = field_chars
+        .position(|c| *c == 'E')
+        .ok_or_else(|| ParseError::end_of_input(input, "the best signal 'E'"))?;
+
+    let starts = field_chars
+        .iter()
+        .filter(|(_, c)| is_start(**c))
+        .map(|(point, _)| point);
+
+    Ok(search::bfs(
+        starts,
+        |&point| {
+            field
+                .neighbors4(point, Bounds::Clip)
+                .filter(|&neighbor| field[neighbor] <= field[point] + 1)
+                .collect::<Vec<_>>()
+        },
+        |&point| point == end,
+    ))
+}
+
+/// Length of the shortest climb to 'E', or -1 if it can't be reached.
+pub fn shortest_path(input: &str, is_start: impl Fn(char) -> bool) -> Result<i32, ParseError> {
+    Ok(climb(input, is_start)?.goal_cost().map_or(-1, |steps| steps as i32))
+}
+
+/// Part 1 climbs from `S`, part 2 from whichever square at height `a` is
+/// closest.
+pub struct Day12;
+
+impl Solution for Day12 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        Ok(shortest_path(input, |c| c == 'S')?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        Ok(shortest_path(input, |c| char_to_height(c) == 0)?.to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate::generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path() {
+        let sample = include_str!("../sample.txt");
+        let path = climb(sample, |c| c == 'S').unwrap().goal_path().unwrap();
+        assert_eq!(path.len(), 32);
+        assert_eq!(path[0], Point::new(0, 0));
+        assert_eq!(path[31], Point::new(2, 5));
+
+        let field = Grid::parse(sample, "a height", Some).unwrap();
+        let heights: Vec<i32> = path.iter().map(|p| char_to_height(field[*p])).collect();
+        assert!(heights.windows(2).all(|w| w[1] <= w[0] + 1));
+    }
+}
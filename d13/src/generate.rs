@@ -0,0 +1,53 @@
+use super::Value;
+use aoc::Rng;
+
+/// `size` pairs of packets. Often the second one is a slightly changed copy
+/// of the first, so the comparison has to look deep.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let pairs: Vec<String> = (0..size.max(1))
+        .map(|_| {
+            let first = packet(rng, 0);
+            let second = if rng.chance(0.5) {
+                changed(rng, &first)
+            } else {
+                packet(rng, 0)
+            };
+            format!("{}\n{}\n", first, second)
+        })
+        .collect();
+    pairs.join("\n")
+}
+
+// A list of up to four values, nested at most four lists deep
+fn packet(rng: &mut Rng, depth: usize) -> Value {
+    let length = rng.below(5);
+    Value::List((0..length).map(|_| value(rng, depth + 1)).collect())
+}
+
+fn value(rng: &mut Rng, depth: usize) -> Value {
+    if depth < 4 && rng.chance(0.3) {
+        packet(rng, depth)
+    } else {
+        Value::Int(rng.between(0, 10) as i32)
+    }
+}
+
+// The same value with one number changed, or wrapped in a list, or a value
+// appended to one of the lists
+fn changed(rng: &mut Rng, value: &Value) -> Value {
+    match value {
+        Value::Int(n) if rng.chance(0.5) => Value::List(vec![Value::Int(*n)]),
+        Value::Int(_) => Value::Int(rng.between(0, 10) as i32),
+        Value::List(list) if list.is_empty() || rng.chance(0.2) => {
+            let mut list = list.clone();
+            list.push(Value::Int(rng.between(0, 10) as i32));
+            Value::List(list)
+        }
+        Value::List(list) => {
+            let mut list = list.clone();
+            let i = rng.below(list.len());
+            list[i] = changed(rng, &list[i]);
+            Value::List(list)
+        }
+    }
+}
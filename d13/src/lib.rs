@@ -0,0 +1,196 @@
+//! Day 13: Distress Signal. Orders the packets by their nested lists.
+
+use aoc::{Cursor, ParseError, Rng, Solution};
+use std::cmp::Ordering;
+
+mod generate;
+
+/// A packet or a part of it. Values are ordered the way the puzzle compares
+/// packets, and print the way they are written.
+#[derive(Debug, Clone)]
+pub enum Value {
+    Int(i32),
+    List(Vec<Value>),
+}
+
+/// Parses a packet like `[1,[2,3],[]]` into a Value.
+pub fn parse(cursor: &mut Cursor) -> Result<Value, ParseError> {
+    if cursor.peek() != Some('[') {
+        let value = cursor.number().map_err(|_| cursor.error("a number or '['"))?;
+        return Ok(Value::Int(value));
+    }
+
+    cursor.tag("[")?;
+    let mut list = Vec::new();
+    if cursor.peek() == Some(']') {
+        cursor.tag("]")?;
+        return Ok(Value::List(list));
+    }
+
+    loop {
+        list.push(parse(cursor)?);
+        if cursor.one_of(",]")? == ']' {
+            return Ok(Value::List(list));
+        }
+    }
+}
+
+/// All packets of the input, the empty lines between the pairs are skipped.
+pub fn parse_packets(input: &str) -> Result<Vec<Value>, ParseError> {
+    aoc::parse::each(aoc::parse::cursors(input).filter(|x| !x.is_empty()), parse)
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Value::Int(i) => write!(f, "{}", i),
+            Value::List(l) => {
+                write!(f, "[")?;
+                for (i, v) in l.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", v)?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+#[test]
+fn test_parse() {
+    let input = include_str!("../input").lines();
+    for line in input {
+        if line.is_empty() {
+            continue;
+        }
+        let parsed = parse(&mut Cursor::new(1, line)).unwrap();
+        let unparsed = format!("{}", parsed);
+        assert_eq!(line, unparsed);
+    }
+}
+
+#[test]
+fn test_parse_errors() {
+    let error = parse(&mut Cursor::new(1, "[1,[2]")).unwrap_err();
+    assert_eq!(error, ParseError::new(1, 7, "one of ',', ']'", "end of line"));
+
+    let error = parse(&mut Cursor::new(1, "[1,,2]")).unwrap_err();
+    assert_eq!(error, ParseError::new(1, 4, "a number or '['", "\",2]\""));
+}
+
+impl Ord for Value {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        fn wrap_in_list(v: &Value) -> Value {
+            Value::List(vec![v.clone()])
+        }
+
+        match (self, other) {
+            (Value::Int(a), Value::Int(b)) => a.cmp(b),
+            (Value::List(a), Value::List(b)) => {
+                for (a, b) in a.iter().zip(b.iter()) {
+                    let cmp = a.cmp(b);
+                    if cmp != std::cmp::Ordering::Equal {
+                        return cmp;
+                    }
+                }
+                a.len().cmp(&b.len())
+            }
+            (Value::Int(_), Value::List(_)) => {
+                wrap_in_list(self).cmp(other)
+            }
+            (Value::List(_), Value::Int(_)) => {
+                self.cmp(&wrap_in_list(other))
+            }
+        }
+    }
+}
+
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Value { }
+
+/// Part 1 sums the indices of the pairs in the right order, part 2 multiplies
+/// the positions of the divider packets once all packets are sorted.
+pub struct Day13;
+
+impl Solution for Day13 {
+    // Sum of the indices of the pairs that are already in the right order
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        let values = parse_packets(input)?;
+        if values.len() % 2 == 1 {
+            return Err(ParseError::end_of_input(input, "the second packet of the pair"));
+        }
+
+        let answer: usize = values
+            .chunks(2)
+            .enumerate()
+            .filter(|(_, pair)| pair[0] < pair[1])
+            .map(|(i, _)| i + 1)
+            .sum();
+
+        Ok(answer.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        let mut values = parse_packets(input)?;
+
+        let key1 = Value::List(vec![Value::List(vec![Value::Int(2)])]);
+        let key2 = Value::List(vec![Value::List(vec![Value::Int(6)])]);
+
+        values.push(key1.clone());
+        values.push(key2.clone());
+
+        values.sort();
+
+        let key1_index = values.iter().position(|x| x == &key1).unwrap() as i32;
+        let key2_index = values.iter().position(|x| x == &key2).unwrap() as i32;
+
+        let answer = (key1_index + 1) * (key2_index + 1);
+        Ok(answer.to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate::generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc::Rng;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn test_generated_packets(seed in any::<u64>(), size in 1..20usize) {
+            let input = generate::generate(&mut Rng::new(seed), size);
+
+            // Every packet prints back the way it was written
+            for line in input.lines().filter(|line| !line.is_empty()) {
+                prop_assert_eq!(parse(&mut Cursor::new(1, line)).unwrap().to_string(), line);
+            }
+
+            // Sorting puts each divider packet right after the packets smaller
+            // than it, and the first divider is smaller than the second
+            let packets = parse_packets(&input).unwrap();
+            let before = |key: &str| {
+                let key = parse(&mut Cursor::new(1, key)).unwrap();
+                packets.iter().filter(|packet| **packet < key).count()
+            };
+            let expected = (before("[[2]]") + 1) * (before("[[6]]") + 2);
+            prop_assert_eq!(Day13.part2(&input).unwrap(), expected.to_string());
+        }
+    }
+}
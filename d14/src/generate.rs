@@ -0,0 +1,28 @@
+use aoc::{Point, Rng};
+
+/// `size` paths of rock under the source of the sand.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            let mut point = Point::new(rng.between(2, 40) as i32, rng.between(470, 530) as i32);
+            let mut points = vec![point];
+            let mut horizontal = rng.chance(0.5);
+
+            for _ in 0..rng.between(1, 4) {
+                let length = rng.between(1, 6) as i32 * if rng.chance(0.5) { 1 } else { -1 };
+                point = if horizontal {
+                    point.advanced(0, length)
+                } else {
+                    point.advanced(length, 0)
+                };
+                // Rock never reaches up to the source
+                point.row = point.row.max(1);
+                points.push(point);
+                horizontal = !horizontal;
+            }
+
+            let points: Vec<String> = points.iter().map(|p| format!("{},{}", p.col, p.row)).collect();
+            points.join(" -> ") + "\n"
+        })
+        .collect()
+}
@@ -0,0 +1,127 @@
+//! Day 14: Regolith Reservoir. Pours sand into the cave until it stops.
+
+use aoc::visual::Frame;
+use aoc::{Cursor, Grid, ParseError, Point, Recorder, Rng, Solution};
+
+mod generate;
+
+fn parse_point(cursor: &mut Cursor) -> Result<Point, ParseError> {
+    let col = cursor.number()?;
+    cursor.tag(",")?;
+    let row = cursor.number()?;
+    Ok(Point { row, col })
+}
+
+/// A path of rock, e.g. `498,4 -> 498,6 -> 496,6`. The first number of a
+/// point is its column.
+pub fn parse_line(cursor: &mut Cursor) -> Result<Vec<Point>, ParseError> {
+    let mut points = vec![parse_point(cursor)?];
+    while !cursor.is_empty() {
+        cursor.tag(" -> ")?;
+        points.push(parse_point(cursor)?);
+    }
+    Ok(points)
+}
+
+// The part of the cave between `min_col` and `max_col`, both included
+fn cave_frame(field: &Grid<char>, min_col: i32, max_col: i32) -> Frame {
+    let mut frame = Grid::new(field.height(), (max_col - min_col + 1) as usize, '.');
+    for point in frame.points() {
+        frame[point] = field[point.advanced(0, min_col)];
+    }
+    frame
+}
+
+/// Number of units of sand that come to rest. Without the `floor` sand starts
+/// falling into the abyss once it passes the lowest rock. Every unit of sand
+/// is a frame for `recorder`.
+pub fn pour_sand(input: &str, floor: bool, recorder: &mut Recorder) -> Result<usize, ParseError> {
+    let chunks = aoc::parse::lines(input, parse_line)?;
+    if chunks.is_empty() {
+        return Err(ParseError::end_of_input(input, "a path of rock"));
+    }
+    let rows = chunks.iter().flat_map(|x| x.iter()).map(|x| x.row).max().unwrap() as usize + 3;
+    let cols = 2*chunks.iter().flat_map(|x| x.iter()).map(|x| x.col).max().unwrap() as usize;
+
+    // The columns the frames show. With a floor the sand spreads out to the
+    // sides as far as it falls.
+    let (min_col, max_col) = if floor {
+        (0.max(500 - rows as i32), (cols as i32 - 1).min(500 + rows as i32))
+    } else {
+        let cols = chunks.iter().flatten().map(|p| p.col);
+        (cols.clone().min().unwrap() - 1, cols.max().unwrap() + 1)
+    };
+
+    let mut field = Grid::new(rows, cols, '.');
+    field.row_mut(rows - 1).fill('#');
+
+    for points in chunks {
+        points.iter()
+            .zip(points.iter().skip(1))
+            .for_each(|(p1, p2)| {
+                let make_range = |l, r| if l < r { l..=r } else { r..=l };
+                if p1.row == p2.row {
+                    for col in make_range(p1.col, p2.col) {
+                        field[Point::new(p1.row, col)] = '#';
+                    }
+                } else {
+                    for row in make_range(p1.row, p2.row) {
+                        field[Point::new(row, p1.col)] = '#';
+                    }
+                }
+            });
+    }
+
+    let mut cnt = 0;
+
+    let source = Point::new(0, 500);
+    while field[source] == '.' {
+        let mut sand = source;
+        loop {
+            let attempts: [i32; 3] = [0, -1, 1];
+
+            let next = attempts
+                .iter()
+                .map(|&dcol| sand.advanced(1, dcol))
+                .find(|&p| field[p] == '.');
+
+            match next {
+                Some(next) => sand = next,
+                None => break,
+            }
+        }
+        // Only the floor is below this row
+        if !floor && sand.row as usize == rows - 2 {
+            break;
+        }
+
+        field[sand] = 'o';
+        cnt += 1;
+        recorder.capture(|| cave_frame(&field, min_col, max_col));
+    }
+
+    Ok(cnt)
+}
+
+/// Part 1 pours until sand falls into the abyss, part 2 until it blocks the
+/// source on a floor below the rocks.
+pub struct Day14;
+
+impl Solution for Day14 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        Ok(pour_sand(input, false, &mut Recorder::off())?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        Ok(pour_sand(input, true, &mut Recorder::off())?.to_string())
+    }
+
+    fn record(&self, input: &str, recorder: &mut Recorder) -> Result<(), ParseError> {
+        pour_sand(input, false, recorder)?;
+        Ok(())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate::generate(rng, size)
+    }
+}
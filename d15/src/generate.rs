@@ -0,0 +1,34 @@
+use aoc::{Point, Rng};
+
+/// `size` sensors around the 20 by 20 area of the sample. A distress beacon
+/// is hidden in the area, every sensor's beacon is closer to it than that.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let distress = random_point(rng, 0, 20);
+
+    let mut output = String::new();
+    let mut placed = 0;
+    while placed < size.max(1) {
+        let sensor = random_point(rng, -5, 25);
+        let reach = sensor.manhattan(&distress) - 1;
+        if reach < 1 {
+            continue;
+        }
+
+        let distance = rng.between(1, reach as i64) as i32;
+        let col = rng.between(-(distance as i64), distance as i64) as i32;
+        let row = (distance - col.abs()) * if rng.chance(0.5) { 1 } else { -1 };
+        let beacon = sensor.advanced(row, col);
+
+        output += &format!(
+            "Sensor at x={}, y={}: closest beacon is at x={}, y={}\n",
+            sensor.col, sensor.row, beacon.col, beacon.row
+        );
+        placed += 1;
+    }
+
+    output
+}
+
+fn random_point(rng: &mut Rng, low: i64, high: i64) -> Point {
+    Point::new(rng.between(low, high) as i32, rng.between(low, high) as i32)
+}
@@ -0,0 +1,290 @@
+//! Day 15: Beacon Exclusion Zone. Works out where the sensors rule out
+//! beacons, one row at a time.
+
+use aoc::{Cursor, ParseError, Point, Rng, Solution};
+use std::cmp::{max, min};
+use std::collections::HashSet;
+use std::ops::Range;
+
+mod generate;
+
+/// A sensor and the beacon closest to it. No other beacon is as close to the
+/// sensor, by Manhattan distance.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Measurement {
+    pub sensor: Point,
+    pub beacon: Point,
+}
+
+fn parse_point(cursor: &mut Cursor) -> Result<Point, ParseError> {
+    cursor.tag("x=")?;
+    let col = cursor.number()?;
+    cursor.tag(", y=")?;
+    let row = cursor.number()?;
+    Ok(Point { col, row })
+}
+
+/// A line like `Sensor at x=2, y=18: closest beacon is at x=-2, y=15`. `x` is
+/// the column and `y` the row.
+pub fn parse_message(cursor: &mut Cursor) -> Result<Measurement, ParseError> {
+    cursor.tag("Sensor at ")?;
+    let sensor = parse_point(cursor)?;
+    cursor.tag(": closest beacon is at ")?;
+    let beacon = parse_point(cursor)?;
+
+    Ok(Measurement { sensor, beacon })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_message() {
+        let msg = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15";
+        let m = parse_message(&mut Cursor::new(1, msg));
+        assert_eq!(
+            m,
+            Ok(Measurement {
+                sensor: Point { col: 2, row: 18 },
+                beacon: Point { col: -2, row: 15 },
+            })
+        );
+
+        let msg = "Sensor at x=2, y=18: closest beacon is at x=-2 y=15";
+        let m = parse_message(&mut Cursor::new(3, msg));
+        assert_eq!(m, Err(ParseError::new(3, 47, "\", y=\"", "' '")));
+    }
+
+    use aoc::Rng;
+    use proptest::prelude::*;
+
+    fn generated(seed: u64, size: usize) -> Vec<Measurement> {
+        let input = generate::generate(&mut Rng::new(seed), size);
+        aoc::parse::lines(&input, parse_message).unwrap()
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(1000))]
+
+        #[test]
+        fn test_fast_matches_slow(seed in any::<u64>(), size in 1..12usize, row in -10..30i32) {
+            let measurements = generated(seed, size);
+            prop_assert_eq!(find_answer_fast(&measurements, row), find_answer_slow(&measurements, row));
+        }
+
+        #[test]
+        fn test_distress_beacon_out_of_reach(seed in any::<u64>(), size in 1..12usize) {
+            let measurements = generated(seed, size);
+            let frequency = find_tuning_frequency(&measurements, 20);
+            let distress = Point::new((frequency % 4000000) as i32, (frequency / 4000000) as i32);
+            for m in &measurements {
+                prop_assert!(m.sensor.manhattan(&distress) > m.sensor.manhattan(&m.beacon));
+            }
+        }
+    }
+}
+
+fn find_position_slow(measurements: &[Measurement], row: i32) -> Vec<i32> {
+    let min_col = measurements
+        .iter()
+        .map(|m| min(m.sensor.col, m.beacon.col))
+        .min()
+        .unwrap();
+
+    let max_col = measurements
+        .iter()
+        .map(|m| max(m.sensor.col, m.beacon.col))
+        .max()
+        .unwrap();
+
+    let d = max_col - min_col + 1;
+
+    let d = 3 * d + row.abs() * 2 + 100;
+    let left_edge = min_col - d;
+    let right_edge = max_col + d;
+
+    let blocked = |col: i32| {
+        let blocked = measurements.iter().any(|m| {
+            let distance_to_candidate = m.sensor.manhattan(&Point { col, row });
+            let distance_to_beacon = m.sensor.manhattan(&m.beacon);
+
+            distance_to_candidate <= distance_to_beacon
+        });
+
+        let has_beacon = measurements
+            .iter()
+            .any(|m| m.beacon.col == col && m.beacon.row == row);
+
+        blocked && !has_beacon
+    };
+
+    (left_edge..right_edge)
+        .filter(|col| blocked(*col))
+        .collect()
+}
+
+#[allow(dead_code)]
+fn find_answer_slow(measurements: &[Measurement], row: i32) -> i32 {
+    let position = find_position_slow(measurements, row);
+    position.len() as i32
+}
+
+/// The columns of `row` that some sensor covers, as sorted ranges that don't
+/// overlap.
+pub fn find_positions_fast(measurements: &[Measurement], row: i32) -> Vec<Range<i32>> {
+    let mut ranges = Vec::new();
+
+    for m in measurements.iter() {
+        let distance_to_beacon = m.sensor.manhattan(&m.beacon);
+
+        let distance_to_row = (m.sensor.row - row).abs();
+
+        if distance_to_row <= distance_to_beacon {
+            let dleft = distance_to_beacon - distance_to_row;
+            let left = m.sensor.col - dleft;
+            let right = m.sensor.col + dleft;
+            ranges.push(left..(right + 1));
+        }
+    }
+
+    ranges.sort_by_key(|r| (r.start, r.end));
+
+    if ranges.is_empty() {
+        return vec![];
+    }
+
+    let mut merged: Vec<Range<i32>> = vec![ranges[0].clone()];
+
+    for r in ranges.iter().skip(1) {
+        let last = merged.last_mut().unwrap();
+        if r.start <= last.end {
+            last.end = max(r.end, last.end);
+        } else {
+            merged.push(r.clone());
+        }
+    }
+
+    merged.sort_by_key(|r| r.start);
+    merged
+}
+
+/// Number of positions in `row` where there can't be a beacon.
+pub fn find_answer_fast(measurements: &[Measurement], row: i32) -> i32 {
+    let ranges = find_positions_fast(measurements, row);
+    let mut beacons = HashSet::new();
+
+    for m in measurements.iter() {
+        if m.beacon.row == row {
+            beacons.insert(m.beacon.col);
+        }
+    }
+    let mut answer = ranges.iter().map(|r| r.end - r.start).sum();
+
+    for b in beacons.iter() {
+        for r in ranges.iter() {
+            if r.contains(b) {
+                answer -= 1;
+                break;
+            }
+        }
+    }
+    answer
+}
+#[cfg(test)]
+mod sample_tests {
+    use super::*;
+
+    #[test]
+    fn test_slow() {
+        let small: Vec<Measurement> = aoc::parse::lines(include_str!("../sample.txt"), parse_message).unwrap();
+
+        assert_eq!(find_answer_slow(&small, 10), 26);
+    }
+
+    #[test]
+    #[ignore]
+    fn test_slow_large() {
+        let large: Vec<Measurement> = aoc::parse::lines(include_str!("../input"), parse_message).unwrap();
+
+        assert_eq!(find_answer_slow(&large, 2000000), 4582667);
+    }
+
+    #[test]
+    fn test_fast_small() {
+        let small: Vec<Measurement> = aoc::parse::lines(include_str!("../sample.txt"), parse_message).unwrap();
+
+        assert_eq!(find_answer_fast(&small, 10), 26);
+    }
+
+    #[test]
+    fn test_fast_small_compare_with_slow() {
+        let small: Vec<Measurement> = aoc::parse::lines(include_str!("../sample.txt"), parse_message).unwrap();
+
+        for row in 0..20 {
+            assert_eq!(
+                find_answer_fast(&small, row),
+                find_answer_slow(&small, row),
+                "Differs at row {}",
+                row
+            );
+        }
+    }
+
+    #[test]
+    fn test_fast_large() {
+        let large: Vec<Measurement> = aoc::parse::lines(include_str!("../input"), parse_message).unwrap();
+
+        assert_eq!(find_answer_fast(&large, 2000000), 4582667);
+    }
+}
+
+// The sample is scanned at row 10 in a 20x20 area, the real input at row
+// 2000000 in a 4000000x4000000 one
+fn is_sample(measurements: &[Measurement]) -> bool {
+    measurements
+        .iter()
+        .all(|m| m.sensor.col <= 100 && m.sensor.row <= 100)
+}
+
+/// Tuning frequency of the only position with both coordinates from 0 to
+/// `max_position` that no sensor covers: its column times 4000000 plus its
+/// row.
+pub fn find_tuning_frequency(measurements: &[Measurement], max_position: i32) -> i64 {
+    for row in 0..(max_position+1) {
+        let ranges = find_positions_fast(measurements, row);
+        let mut possible = 0;
+        for range in ranges.iter() {
+            if range.contains(&possible) {
+                assert!(possible == 0);
+                possible = range.end;
+            }
+        }
+        if possible <= max_position {
+            return (possible as i64)*4000000 + (row as i64);
+        }
+    }
+    panic!("No position for the distress beacon");
+}
+
+/// Part 1 counts the positions without a beacon in row 2000000, part 2 finds
+/// the distress beacon. The sample uses row 10 and a 20x20 area instead.
+pub struct Day15;
+
+impl Solution for Day15 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        let measurements = aoc::parse::lines(input, parse_message)?;
+        let row = if is_sample(&measurements) { 10 } else { 2000000 };
+        Ok(find_answer_fast(&measurements, row).to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        let measurements = aoc::parse::lines(input, parse_message)?;
+        let max_position = if is_sample(&measurements) { 20 } else { 4000000 };
+        Ok(find_tuning_frequency(&measurements, max_position).to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate::generate(rng, size)
+    }
+}
@@ -0,0 +1,50 @@
+use aoc::Rng;
+use std::collections::BTreeSet;
+
+/// A scan of `size` valves connected by tunnels, starting at AA. At most 8
+/// of them have a flow rate, as the valves that do are kept in a bit mask.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let count = size.clamp(2, 26 * 26);
+
+    let mut names = BTreeSet::from(["AA".to_string()]);
+    while names.len() < count {
+        let letter = |rng: &mut Rng| (b'A' + rng.below(26) as u8) as char;
+        names.insert(format!("{}{}", letter(rng), letter(rng)));
+    }
+    let mut names: Vec<String> = names.into_iter().collect();
+    rng.shuffle(&mut names);
+
+    // A random tree, so every valve can be reached, and a few shortcuts
+    let mut tunnels: Vec<BTreeSet<usize>> = vec![BTreeSet::new(); count];
+    let mut connect = |a: usize, b: usize| {
+        if a != b {
+            tunnels[a].insert(b);
+            tunnels[b].insert(a);
+        }
+    };
+    for i in 1..count {
+        connect(i, rng.below(i));
+    }
+    for _ in 0..count / 3 {
+        connect(rng.below(count), rng.below(count));
+    }
+
+    let mut flows = vec![0; count];
+    let mut working: Vec<usize> = (0..count).filter(|&i| names[i] != "AA").collect();
+    rng.shuffle(&mut working);
+    for &i in working.iter().take(8) {
+        flows[i] = rng.between(1, 25);
+    }
+
+    (0..count)
+        .map(|i| {
+            let to: Vec<&str> = tunnels[i].iter().map(|&j| names[j].as_str()).collect();
+            let lead = if to.len() == 1 {
+                "tunnel leads to valve"
+            } else {
+                "tunnels lead to valves"
+            };
+            format!("Valve {} has flow rate={}; {} {}\n", names[i], flows[i], lead, to.join(", "))
+        })
+        .collect()
+}
@@ -0,0 +1,255 @@
+//! Day 16: Proboscidea Volcanium. Plans which valves to open, alone or with
+//! the elephant's help.
+
+use aoc::{Cursor, ParseError, Rng, Solution};
+use std::collections::HashMap;
+
+mod generate;
+
+/// A valve from the scan, with the valves its tunnels lead to.
+#[derive(Debug)]
+pub struct VertexInfoStr {
+    pub name: String,
+    pub flow: i32,
+    pub edges_to: Vec<String>,
+    // what to report for each of `edges_to` if there is no such valve
+    unknown_edges: Vec<ParseError>,
+}
+
+/// A line like `Valve AA has flow rate=0; tunnels lead to valves DD, II, BB`.
+pub fn parse_info_line(cursor: &mut Cursor) -> Result<VertexInfoStr, ParseError> {
+    cursor.tag("Valve ")?;
+    let name = cursor.word()?.to_string();
+    cursor.tag(" has flow rate=")?;
+    let flow = cursor.number()?;
+
+    if cursor.rest().starts_with("; tunnels") {
+        cursor.tag("; tunnels lead to valves ")?;
+    } else {
+        cursor.tag("; tunnel leads to valve ")?;
+    }
+
+    let mut edges_to = Vec::new();
+    let mut unknown_edges = Vec::new();
+    loop {
+        unknown_edges.push(cursor.error("a valve from the scan"));
+        edges_to.push(cursor.word()?.to_string());
+        if cursor.is_empty() {
+            break;
+        }
+        cursor.tag(", ")?;
+    }
+
+    Ok(VertexInfoStr {
+        name,
+        flow,
+        edges_to,
+        unknown_edges,
+    })
+}
+
+/// All valves of the scan. Every tunnel has to lead to one of them, and one
+/// has to be AA, where the search starts.
+pub fn parse_info_lines(input: &str) -> Result<Vec<VertexInfoStr>, ParseError> {
+    let info = aoc::parse::lines(input, parse_info_line)?;
+
+    for v in info.iter() {
+        for (edge, error) in v.edges_to.iter().zip(v.unknown_edges.iter()) {
+            if !info.iter().any(|other| other.name == *edge) {
+                return Err(error.clone());
+            }
+        }
+    }
+
+    if !info.iter().any(|v| v.name == "AA") {
+        return Err(ParseError::end_of_input(input, "valve AA"));
+    }
+
+    Ok(info)
+}
+
+struct VertexInfoIdx {
+    name: usize,
+    flow: i32,
+    edges_to: Vec<usize>,
+}
+
+enum Action {
+    Stay,
+    Move(usize),
+    TurnOn(usize, i32),
+}
+
+type State = (usize, usize, usize, usize);
+
+fn apply_action(s: State, my: &Action, elephant: &Action) -> (State, i32) {
+    let (ct, mut my_node, mut elephant_node, mut mask) = s;
+
+    match (my, elephant) {
+        (Action::TurnOn(p1, _), Action::TurnOn(p2, _)) if p1 == p2 => return (s, -1000000),
+        _ => (),
+    }
+
+    fn turn_on(mask: usize, position: usize) -> usize {
+        assert!(mask & (1 << position) == 0);
+        mask | (1 << position)
+    }
+
+    let mut value: i32 = 0;
+
+    match my {
+        Action::Stay => (),
+        Action::Move(next_node) => my_node = *next_node,
+        Action::TurnOn(position, b) => {
+            value += b;
+            mask = turn_on(mask, *position);
+        },
+    }
+
+    match elephant {
+        Action::Stay => (),
+        Action::Move(next_node) => elephant_node = *next_node,
+        Action::TurnOn(position, b) => {
+            value += b;
+            mask = turn_on(mask, *position);
+        },
+    }
+
+    ((ct + 1, my_node, elephant_node, mask), value)
+}
+
+// Both agents are interchangeable, so only one of (a, b) and (b, a) is kept
+fn canonical(s: State) -> State {
+    let (ct, my_node, elephant_node, mask) = s;
+    let my_new_node = std::cmp::min(my_node, elephant_node);
+    let elephant_new_node = std::cmp::max(my_node, elephant_node);
+    (ct, my_new_node, elephant_new_node, mask)
+}
+
+/// Most pressure that can be released in `time` minutes, alone or working
+/// together with the elephant.
+#[allow(clippy::needless_range_loop)]
+pub fn max_pressure(input: &str, time: usize, with_elephant: bool) -> Result<i32, ParseError> {
+    let info = parse_info_lines(input)?;
+
+    let name_to_index: HashMap<String, usize> = info
+        .iter()
+        .enumerate()
+        .map(|(i, v)| (v.name.clone(), i))
+        .collect();
+
+    let vertices = {
+        let mut vertices = info
+            .iter()
+            .map(|v| VertexInfoIdx {
+                name: name_to_index[&v.name],
+                flow: v.flow,
+                edges_to: v.edges_to.iter().map(|s| name_to_index[s]).collect(),
+            })
+            .collect::<Vec<_>>();
+        vertices.sort_by_key(|v| v.name);
+        vertices
+    };
+
+    for (i, v) in vertices.iter().enumerate() {
+        assert_eq!(i, v.name);
+    }
+
+    let interesting_nodes = vertices.iter().filter(|v| v.flow > 0).collect::<Vec<_>>();
+
+    let node_to_mask_position = {
+        let mut result = vec![100; vertices.len()];
+        for (i, node) in interesting_nodes.iter().enumerate() {
+            result[node.name] = i;
+        }
+        result
+    };
+
+    let mask_len = interesting_nodes.len();
+    let max_mask = 1 << mask_len;
+
+    let start_node = name_to_index["AA"];
+    let node_count = vertices.len();
+
+    // my_node, elephant_node, mask; only the current and the next minute are kept
+    let empty_layer = vec![vec![vec![-5000000; max_mask + 1]; node_count]; node_count];
+    let mut dp = [empty_layer.clone(), empty_layer];
+
+    dp[0][start_node][start_node][0] = 0;
+
+    for ct in 0..(time - 1) {
+        aoc::progress!("minute {}/{}", ct + 1, time - 1);
+        let [current, next] = &mut dp;
+        for my_node in 0..node_count {
+            for elephant_node in 0..node_count {
+                for mask in 0..max_mask {
+                    if current[my_node][elephant_node][mask] < 0 {
+                        continue;
+                    }
+
+                    let actions_from_position = |position: usize| {
+                        let mut actions = vec![];
+                        for &next_node in vertices[position].edges_to.iter() {
+                            actions.push(Action::Move(next_node));
+                        }
+
+                        let mask_position = node_to_mask_position[position];
+                        if mask_position < 100
+                            && mask & (1 << mask_position) == 0 {
+                                let time_left = (time - ct - 1) as i32;
+                                let benefit = vertices[position].flow * time_left;
+                                actions.push(Action::TurnOn(mask_position, benefit));
+                            }
+                        actions
+                    };
+
+                    let my_actions = actions_from_position(my_node);
+                    let elephant_actions = if with_elephant {
+                        actions_from_position(elephant_node)
+                    } else {
+                        vec![Action::Stay]
+                    };
+
+                    let state = (ct, my_node, elephant_node, mask);
+                    let cur_value = current[my_node][elephant_node][mask];
+                    for my_action in my_actions.iter() {
+                        for elephant_action in elephant_actions.iter() {
+                            let (mut state, benefit) = apply_action(state, my_action, elephant_action);
+                            if with_elephant {
+                                state = canonical(state);
+                            }
+                            let (_, new_my_node, new_elephant_node, new_mask) = state;
+                            let new_value = &mut next[new_my_node][new_elephant_node][new_mask];
+                            *new_value = std::cmp::max(*new_value, cur_value + benefit);
+                        }
+                    }
+                }
+            }
+        }
+
+        for value in current.iter_mut().flat_map(|v| v.iter_mut()).flat_map(|v| v.iter_mut()) {
+            *value = -5000000;
+        }
+        dp.swap(0, 1);
+    }
+
+    Ok(*dp[0].iter().flat_map(|v| v.iter()).flat_map(|v| v.iter()).max().unwrap())
+}
+
+/// Part 1 has 30 minutes alone, part 2 26 minutes together with the
+/// elephant.
+pub struct Day16;
+
+impl Solution for Day16 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        Ok(max_pressure(input, 30, false)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        Ok(max_pressure(input, 26, true)?.to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate::generate(rng, size)
+    }
+}
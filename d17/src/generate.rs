@@ -0,0 +1,9 @@
+use aoc::Rng;
+
+/// A jet pattern `size` pushes long.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| *rng.pick(&['<', '>']))
+        .collect::<String>()
+        + "\n"
+}
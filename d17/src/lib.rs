@@ -0,0 +1,424 @@
+//! Day 17: Pyroclastic Flow. Drops the rocks into the chamber and watches
+//! the tower grow.
+
+use aoc::cycle::{self, Cycle};
+use aoc::visual::Frame;
+use aoc::{Grid, ParseError, Point, Recorder, Rng, Solution};
+use std::fmt;
+
+mod generate;
+
+/// The shape of a rock, as the points it covers. Row 0 is its bottom and
+/// column 0 its left edge, and rows grow upwards.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Figure {
+    pub points: Vec<Point>,
+}
+
+impl fmt::Display for Figure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cols = self.points.iter().map(|p| p.col).max().unwrap() + 1;
+        let rows = self.points.iter().map(|p| p.row).max().unwrap() + 1;
+
+        for row in (0..rows).rev() {
+            for col in 0..cols {
+                if self.points.contains(&Point { row, col }) {
+                    write!(f, "#")?;
+                } else {
+                    write!(f, ".")?;
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Rock {
+    figure: Figure,
+    position: Point,
+}
+
+impl Rock {
+    fn points(&self) -> Vec<Point> {
+        self.figure
+            .points
+            .iter()
+            .map(|p| self.position + *p)
+            .collect()
+    }
+
+    fn advance(&self, drow: i32, dcol: i32) -> Rock {
+        Rock {
+            figure: self.figure.clone(),
+            position: self.position.advanced(drow, dcol),
+        }
+    }
+
+    fn top(&self) -> Point {
+        let mut top = self.position;
+        for p in self.points() {
+            if p.row > top.row {
+                top = p;
+            }
+        }
+        top
+    }
+
+    // Rows above the top of the field are still empty
+    fn is_valid(&self, field: &Grid<bool>) -> bool {
+        for p in self.points() {
+            if p.col < 0 || p.col >= field.width() as i32 {
+                return false;
+            }
+
+            if field.get(p) == Some(&true) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// The five rocks, in the order they fall.
+pub fn figures() -> [Figure; 5] {
+    [
+        Figure {
+            points: vec![
+                Point { row: 0, col: 0 },
+                Point { row: 0, col: 1 },
+                Point { row: 0, col: 2 },
+                Point { row: 0, col: 3 },
+            ],
+        },
+        Figure {
+            points: vec![
+                Point { row: 0, col: 1 },
+                Point { row: 1, col: 0 },
+                Point { row: 1, col: 1 },
+                Point { row: 1, col: 2 },
+                Point { row: 2, col: 1 },
+            ],
+        },
+        Figure {
+            points: vec![
+                Point { row: 0, col: 0 },
+                Point { row: 0, col: 1 },
+                Point { row: 0, col: 2 },
+                Point { row: 1, col: 2 },
+                Point { row: 2, col: 2 },
+            ],
+        },
+        Figure {
+            points: vec![
+                Point { row: 0, col: 0 },
+                Point { row: 1, col: 0 },
+                Point { row: 2, col: 0 },
+                Point { row: 3, col: 0 },
+            ],
+        },
+        Figure {
+            points: vec![
+                Point { row: 0, col: 0 },
+                Point { row: 0, col: 1 },
+                Point { row: 1, col: 0 },
+                Point { row: 1, col: 1 },
+            ],
+        },
+    ]
+}
+
+/// The jet pattern is a single line of '<' and '>'.
+pub fn parse_air(input: &str) -> Result<Vec<char>, ParseError> {
+    let mut cursor = aoc::parse::cursors(input)
+        .next()
+        .ok_or_else(|| ParseError::end_of_input(input, "a jet pattern"))?;
+
+    let mut air = vec![cursor.one_of("<>")?];
+    while !cursor.is_empty() {
+        air.push(cursor.one_of("<>")?);
+    }
+    Ok(air)
+}
+
+/// The chamber with the rocks that came to rest so far. Row 0 is the floor.
+pub struct Tower {
+    field: Grid<bool>,
+    top_row: i32,
+    air_index: usize,
+    figure_index: usize,
+    // How far below the top any rock came to rest, and the tallest rock
+    deepest: i32,
+    tallest: i32,
+}
+
+// How many rows a frame shows, from just above a new rock down
+const FRAME_ROWS: i32 = 40;
+
+// How many rocks, and how many times through the jets and the rocks after
+// them, the tower gets to start repeating
+const CYCLE_START: usize = 10_000;
+const CYCLE_ROUNDS: usize = 20;
+
+impl Tower {
+    /// An empty chamber, waiting for the first rock and the first jet.
+    pub fn new() -> Tower {
+        Tower {
+            field: Grid::new(1, 7, true),
+            top_row: 0,
+            air_index: 0,
+            figure_index: 0,
+            deepest: 0,
+            tallest: 0,
+        }
+    }
+
+    /// Height of the tower, not counting the floor.
+    pub fn height(&self) -> i32 {
+        self.top_row
+    }
+
+    /// Drops the next rock, pushed around by the jets of `air` in turn, and
+    /// returns how much the tower grew. Every step of the rock is a frame for
+    /// `recorder`.
+    pub fn drop_rock(&mut self, air: &[char], figures: &[Figure], recorder: &mut Recorder) -> i32 {
+        let start_top = self.top_row;
+
+        let figure = &figures[self.figure_index];
+        self.figure_index = (self.figure_index + 1) % figures.len();
+        let mut rock = Rock {
+            figure: figure.clone(),
+            position: Point {
+                row: self.top_row + 4,
+                col: 2,
+            },
+        };
+
+        loop {
+            recorder.capture(|| self.frame(&rock));
+
+            let dcol = if air[self.air_index] == '>' { 1 } else { -1 };
+            self.air_index = (self.air_index + 1) % air.len();
+
+            let new_rock = rock.advance(0, dcol);
+            if new_rock.is_valid(&self.field) {
+                rock = new_rock;
+            }
+
+            let new_rock = rock.advance(-1, 0);
+            if new_rock.is_valid(&self.field) {
+                rock = new_rock;
+            } else {
+                break;
+            }
+        }
+
+        self.top_row = std::cmp::max(rock.top().row, self.top_row);
+        let bottom = rock.position.row;
+        self.deepest = self.deepest.max(self.top_row - bottom);
+        self.tallest = self.tallest.max(rock.top().row - bottom + 1);
+
+        while self.field.height() <= (self.top_row + 1) as usize {
+            self.field.push_row([false; 7]);
+        }
+
+        for p in rock.points() {
+            self.field[p] = true;
+        }
+
+        self.top_row - start_top
+    }
+
+    // The top of the chamber with the falling rock drawn as '@'
+    fn frame(&self, rock: &Rock) -> Frame {
+        let top = self.top_row + 7;
+        let rock_points = rock.points();
+        let mut frame = Grid::new(FRAME_ROWS as usize, 9, ' ');
+
+        for i in 0..FRAME_ROWS {
+            let row = top - i;
+            let line = frame.row_mut(i as usize);
+            match row {
+                _ if row < 0 => {}
+                0 => line.copy_from_slice(&['+', '-', '-', '-', '-', '-', '-', '-', '+']),
+                _ => {
+                    line[0] = '|';
+                    line[8] = '|';
+                    for col in 0..7 {
+                        let point = Point { row, col };
+                        line[col as usize + 1] = if rock_points.contains(&point) {
+                            '@'
+                        } else if self.field.get(point) == Some(&true) {
+                            '#'
+                        } else {
+                            '.'
+                        };
+                    }
+                }
+            }
+        }
+        frame
+    }
+
+    // The next figure and jet together with the empty cells a rock could
+    // still get to, row by row down from the top. Rocks only move down and
+    // sideways, and the cells they can't get to are as good as rock. Rows
+    // further down than any rock fell so far, plus the height of the tallest
+    // one, are left out, or a column the jets never fill would make the
+    // fingerprint grow with the tower. That makes it a guess: a later rock
+    // could fall deeper than any before and two towers with the same
+    // fingerprint grow apart. `tower_height` checks the cycle it finds.
+    fn fingerprint(&self) -> (usize, usize, Vec<u8>) {
+        let mut rows = Vec::new();
+        let mut reached = 0b111_1111u8;
+        let bottom = std::cmp::max(self.top_row - self.deepest - self.tallest, 1);
+        for row in (bottom..=self.top_row).rev() {
+            let rocks = self.field.row(row as usize).iter().fold(0, |bits, &rock| bits << 1 | rock as u8);
+            let open = !rocks & 0b111_1111;
+            reached &= open;
+            loop {
+                let spread = (reached | reached << 1 | reached >> 1) & open;
+                if spread == reached {
+                    break;
+                }
+                reached = spread;
+            }
+            if reached == 0 {
+                break;
+            }
+            rows.push(reached);
+        }
+
+        (self.figure_index, self.air_index, rows)
+    }
+}
+
+impl Default for Tower {
+    fn default() -> Tower {
+        Tower::new()
+    }
+}
+
+/// Height of the tower after `rocks` rocks. Once the tower starts repeating
+/// itself the rest is worked out from the cycle, which has to show within
+/// some rounds of every jet and rock, and play out a second time the same
+/// way.
+pub fn tower_height(input: &str, rocks: u64) -> Result<i64, ParseError> {
+    let air = parse_air(input)?;
+    let figures = figures();
+    let limit = (CYCLE_ROUNDS * air.len() * figures.len() + CYCLE_START) as u64;
+    let drop = |tower: &mut Tower| tower.drop_rock(&air, &figures, &mut Recorder::off()) as i64;
+
+    let history = cycle::find(Tower::new(), rocks.min(limit), drop, Tower::fingerprint);
+    let expected = format!("jets that repeat within {} rocks", limit);
+    if history.deltas.len() as u64 >= rocks {
+        return Ok(history.total(rocks));
+    }
+    match history.cycle {
+        None => Err(ParseError::new(1, 1, expected, "no cycle")),
+        Some(cycle) if !repeats(cycle, &history.deltas, drop) => {
+            Err(ParseError::new(1, 1, expected, "a cycle that doesn't repeat"))
+        }
+        Some(_) => Ok(history.total(rocks)),
+    }
+}
+
+// Whether the cycle found with the fingerprint plays out once more the way
+// it did the first time, without a rock falling deeper than any before,
+// which is what the fingerprint can't see.
+fn repeats(cycle: Cycle, deltas: &[i64], mut drop: impl FnMut(&mut Tower) -> i64) -> bool {
+    let Cycle { start, length } = cycle;
+    let mut tower = Tower::new();
+    for _ in 0..start + length {
+        drop(&mut tower);
+    }
+
+    let (deepest, fingerprint) = (tower.deepest, tower.fingerprint());
+    let again: Vec<i64> = (0..length).map(|_| drop(&mut tower)).collect();
+    again == deltas[start..] && tower.deepest == deepest && tower.fingerprint() == fingerprint
+}
+
+/// Part 1 is the height after 2022 rocks, part 2 after 1000000000000.
+pub struct Day17;
+
+impl Solution for Day17 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        Ok(tower_height(input, 2022)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        Ok(tower_height(input, 1000000000000)?.to_string())
+    }
+
+    fn record(&self, input: &str, recorder: &mut Recorder) -> Result<(), ParseError> {
+        let air = parse_air(input)?;
+        let figures = figures();
+        let mut tower = Tower::new();
+        for _ in 0..2022 {
+            tower.drop_rock(&air, &figures, recorder);
+        }
+        Ok(())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate::generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc::Rng;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_repeats() {
+        let air = parse_air(include_str!("../sample.txt")).unwrap();
+        let figures = figures();
+        let drop = |tower: &mut Tower| tower.drop_rock(&air, &figures, &mut Recorder::off()) as i64;
+
+        let history = cycle::find(Tower::new(), 10_000, drop, Tower::fingerprint);
+        let found = history.cycle.unwrap();
+        assert!(repeats(found, &history.deltas, drop));
+
+        // Ten rocks in, the tower hasn't settled into anything yet
+        let mut tower = Tower::new();
+        let deltas: Vec<i64> = (0..10).map(|_| drop(&mut tower)).collect();
+        assert!(!repeats(Cycle { start: 3, length: 7 }, &deltas, drop));
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn test_extrapolated_height(seed in any::<u64>(), size in 1..50usize, rocks in 0..5000u64) {
+            let input = generate::generate(&mut Rng::new(seed), size);
+            let air = parse_air(&input).unwrap();
+            let figures = figures();
+
+            let mut tower = Tower::new();
+            let mut height = 0;
+            for _ in 0..rocks {
+                height += tower.drop_rock(&air, &figures, &mut Recorder::off()) as i64;
+            }
+            prop_assert_eq!(tower_height(&input, rocks).unwrap(), height);
+        }
+
+        // Short patterns can leave a column the rocks never fill, and still
+        // have to repeat before the limit, and repeat the way they drop
+        #[test]
+        fn test_short_patterns(jets in "[<>]{1,6}") {
+            let air = parse_air(&jets).unwrap();
+            let figures = figures();
+
+            let rocks = 30_000;
+            let mut tower = Tower::new();
+            for _ in 0..rocks {
+                tower.drop_rock(&air, &figures, &mut Recorder::off());
+            }
+            prop_assert_eq!(tower_height(&jets, rocks).unwrap(), tower.height() as i64);
+            prop_assert!(tower_height(&jets, 1_000_000_000_000).is_ok());
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use aoc::{Point3, Rng};
+use std::collections::HashSet;
+
+/// A droplet of `size` cubes, packed densely enough in a box to trap some
+/// air inside.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let side = ((size as f64).cbrt() * 1.5).ceil() as i64 + 1;
+
+    let mut cubes = HashSet::new();
+    let mut output = String::new();
+    while cubes.len() < size {
+        let mut coordinate = || rng.between(1, side) as i32;
+        let cube = Point3::new(coordinate(), coordinate(), coordinate());
+        if cubes.insert(cube) {
+            output += &format!("{},{},{}\n", cube.x, cube.y, cube.z);
+        }
+    }
+
+    output
+}
@@ -0,0 +1,90 @@
+//! Day 18: Boiling Boulders. Measures the surface of the lava droplet.
+
+use aoc::Point3 as Cube;
+use aoc::{search, Cursor, ParseError, Rng, Solution};
+use std::collections::HashSet;
+
+mod generate;
+
+/// A cube of lava written as `x,y,z`.
+pub fn parse_cube(cursor: &mut Cursor) -> Result<Cube, ParseError> {
+    let x = cursor.number()?;
+    cursor.tag(",")?;
+    let y = cursor.number()?;
+    cursor.tag(",")?;
+    let z = cursor.number()?;
+    Ok(Cube { x, y, z })
+}
+
+/// Surface area of the droplet, counting the air pockets trapped inside it.
+pub fn surface_area(cubes: &[Cube]) -> usize {
+    let cubes_set: HashSet<Cube> = cubes.iter().cloned().collect();
+
+    cubes
+        .iter()
+        .flat_map(|cube| cube.neighbors())
+        .filter(|cube| !cubes_set.contains(cube))
+        .count()
+}
+
+/// Surface area that the steam flowing around the droplet can reach.
+pub fn exterior_surface_area(cubes: &[Cube]) -> usize {
+    let cubes_set: HashSet<Cube> = cubes.iter().cloned().collect();
+
+    let min_x = cubes.iter().map(|c| c.x).min().unwrap() - 1;
+    let max_x = cubes.iter().map(|c| c.x).max().unwrap() + 3;
+
+    let min_y = cubes.iter().map(|c| c.y).min().unwrap() -1;
+    let max_y = cubes.iter().map(|c| c.y).max().unwrap() + 3;
+
+    let min_z = cubes.iter().map(|c| c.z).min().unwrap() - 1;
+    let max_z = cubes.iter().map(|c| c.z).max().unwrap() + 3;
+
+    let x_range = min_x..max_x;
+    let y_range = min_y..max_y;
+    let z_range = min_z..max_z;
+
+    // Steam spreads from a corner of the box around the droplet; cubes just
+    // outside of the box are reached, but it doesn't spread any further
+    let steam = search::bfs(
+        [Cube { x: min_x, y: min_y, z: min_z }],
+        |cube| {
+            if !x_range.contains(&cube.x) || !y_range.contains(&cube.y) || !z_range.contains(&cube.z) {
+                return vec![];
+            }
+            cube.neighbors()
+                .into_iter()
+                .filter(|neighbor| !cubes_set.contains(neighbor))
+                .collect()
+        },
+        |_| false,
+    );
+
+    let answer = cubes
+        .iter()
+        .flat_map(|cube| cube.neighbors())
+        .filter(|cube| steam.contains(cube))
+        .count();
+
+    answer
+}
+
+/// Part 1 counts every side of a cube that doesn't touch another cube, part 2
+/// only those on the outside of the droplet.
+pub struct Day18;
+
+impl Solution for Day18 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        let cubes = aoc::parse::lines(input, parse_cube)?;
+        Ok(surface_area(&cubes).to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        let cubes = aoc::parse::lines(input, parse_cube)?;
+        Ok(exterior_surface_area(&cubes).to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate::generate(rng, size)
+    }
+}
@@ -0,0 +1,20 @@
+use aoc::Rng;
+
+/// `size` blueprints, with costs in the ranges the puzzle uses.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (1..=size.max(1))
+        .map(|id| {
+            format!(
+                "Blueprint {}: Each ore robot costs {} ore. Each clay robot costs {} ore. \
+                 Each obsidian robot costs {} ore and {} clay. Each geode robot costs {} ore and {} obsidian.\n",
+                id,
+                rng.between(2, 4),
+                rng.between(2, 4),
+                rng.between(2, 4),
+                rng.between(4, 20),
+                rng.between(2, 4),
+                rng.between(4, 20),
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,276 @@
+//! Day 19: Not Enough Minerals. Finds the most geodes the robots of a
+//! blueprint can crack.
+
+use aoc::{Cursor, ParseError, Rng, Solution};
+use std::collections::HashMap;
+
+mod generate;
+
+/// What each kind of robot costs.
+#[derive(Debug)]
+pub struct BluePrint {
+    pub ore_for_ore: i32,
+    pub ore_for_clay: i32,
+    pub ore_for_obsidian: i32,
+    pub clay_for_obsidian: i32,
+    pub ore_for_geode: i32,
+    pub obsidian_for_geode: i32,
+}
+
+/// A line like `Blueprint 1: Each ore robot costs 4 ore. ...`. The number of
+/// the blueprint isn't kept, blueprints are numbered by their line.
+pub fn parse_blueprint(cursor: &mut Cursor) -> Result<BluePrint, ParseError> {
+    cursor.tag("Blueprint ")?;
+    cursor.number::<i32>()?;
+    cursor.tag(": Each ore robot costs ")?;
+    let ore_for_ore = cursor.number()?;
+    cursor.tag(" ore. Each clay robot costs ")?;
+    let ore_for_clay = cursor.number()?;
+    cursor.tag(" ore. Each obsidian robot costs ")?;
+    let ore_for_obsidian = cursor.number()?;
+    cursor.tag(" ore and ")?;
+    let clay_for_obsidian = cursor.number()?;
+    cursor.tag(" clay. Each geode robot costs ")?;
+    let ore_for_geode = cursor.number()?;
+    cursor.tag(" ore and ")?;
+    let obsidian_for_geode = cursor.number()?;
+    cursor.tag(" obsidian.")?;
+
+    Ok(BluePrint {
+        ore_for_ore,
+        ore_for_clay,
+        ore_for_obsidian,
+        clay_for_obsidian,
+        ore_for_geode,
+        obsidian_for_geode,
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct State {
+    ore: i16,
+    ore_robots: i8,
+    clay: i16,
+    clay_robots: i8,
+    obsidian: i16,
+    obsidian_robots: i8,
+}
+
+fn div_up(a: i16, b: i16) -> i16 {
+    a / b + if a % b == 0 { 0 } else { 1 }
+}
+
+fn time_to_ore_amount(state: &State, amount: i32) -> i16 {
+    let amount = amount as i16;
+    if state.ore >= amount {
+        return 0;
+    }
+
+    let needed = amount - state.ore;
+    div_up(needed, state.ore_robots as i16)
+}
+
+fn get_time_to_ore(state: &State, blueprint: &BluePrint) -> i16 {
+    time_to_ore_amount(state, blueprint.ore_for_ore)
+}
+
+fn get_time_to_clay(state: &State, blueprint: &BluePrint) -> i16 {
+    time_to_ore_amount(state, blueprint.ore_for_clay)
+}
+
+fn get_time_to_obsidian(state: &State, blueprint: &BluePrint) -> i16 {
+    if state.clay_robots == 0 {
+        return 100;
+    }
+
+    let time_for_ore = time_to_ore_amount(state, blueprint.ore_for_obsidian);
+
+    if state.clay >= (blueprint.clay_for_obsidian as i16){
+        return time_for_ore;
+    }
+
+    let needed = (blueprint.clay_for_obsidian as i16) - state.clay;
+    let time_for_clay = div_up(needed, state.clay_robots as i16);
+
+    std::cmp::max(time_for_ore, time_for_clay)
+}
+
+fn get_time_to_geode(state: &State, blueprint: &BluePrint) -> i16 {
+    if state.obsidian_robots == 0 {
+        return 100;
+    }
+
+    let time_for_ore = time_to_ore_amount(state, blueprint.ore_for_geode);
+
+    if state.obsidian >= (blueprint.obsidian_for_geode as i16) {
+        return time_for_ore;
+    }
+
+    let needed = (blueprint.obsidian_for_geode as i16) - state.obsidian;
+    let time_for_obsidian = div_up(needed, state.obsidian_robots.into());
+
+    std::cmp::max(time_for_ore, time_for_obsidian)
+}
+
+fn advance_state_in_time(state: &State, time: i16) -> State {
+    State {
+        ore: state.ore + time * (state.ore_robots as i16),
+        clay: state.clay + time * (state.clay_robots as i16),
+        obsidian: state.obsidian + time * (state.obsidian_robots as i16),
+        ..*state
+    }
+}
+
+const INITIAL_STATE: State = State {
+    ore: 0,
+    ore_robots: 1,
+    clay: 0,
+    clay_robots: 0,
+    obsidian: 0,
+    obsidian_robots: 0,
+};
+
+/// Most geodes that can be opened in `time` minutes, starting with a single
+/// ore robot.
+pub fn get_answer(blueprint: &BluePrint, time: usize) -> i16 {
+    let mut states: Vec<HashMap<State, i16>> = vec![HashMap::new(); time + 1];
+    let mut prev_pos = vec![vec![]; time + 1];
+    states[0].insert(INITIAL_STATE, 0);
+    prev_pos[0].push((0, 0));
+    let mut best = 0;
+
+    let max_ore_amount = *([blueprint.ore_for_ore, blueprint.ore_for_clay, blueprint.ore_for_obsidian, blueprint.ore_for_geode].iter().max().unwrap()) as i16;
+
+    for curent_t in 0..time {
+        aoc::debug!("{}: {} states", curent_t, states[curent_t].len());
+        let (left, next_states) = states.split_at_mut(curent_t + 1);
+        let current_states = left.last().unwrap();
+
+        let time_left = time - curent_t;
+        let max_ore_amount = max_ore_amount * (time_left as i16);
+        let max_clay_amount = blueprint.clay_for_obsidian as i16 * (time_left as i16);
+        let max_obsidian_amount = blueprint.obsidian_for_geode as i16 * (time_left as i16);
+
+        let max_geodes_possible = {
+            let itime = time_left as i16;
+            itime*(itime-1)/2
+        };
+
+        let min_geodes = best - max_geodes_possible;
+
+        for (state, geodes) in current_states.iter() {
+            if *geodes < min_geodes {
+                continue;
+            }
+            if state.ore <= max_ore_amount {
+                let time_to_ore = get_time_to_ore(state, blueprint) + 1;
+                let nt = curent_t + (time_to_ore as usize);
+
+                if nt <= time {
+                    let mut new_state = advance_state_in_time(state, time_to_ore);
+                    new_state.ore -= blueprint.ore_for_ore as i16;
+                    new_state.ore_robots += 1;
+                    let current = next_states[time_to_ore as usize - 1].entry(new_state).or_insert(*geodes);
+                    *current = std::cmp::max(*current, *geodes);
+                }
+            }
+
+            if state.clay <= max_clay_amount {
+                let time_to_clay = get_time_to_clay(state, blueprint) + 1;
+                let nt = curent_t + (time_to_clay as usize);
+
+                if nt <= time {
+                    let mut new_state = advance_state_in_time(state, time_to_clay);
+                    new_state.ore -= blueprint.ore_for_clay as i16;
+                    new_state.clay_robots += 1;
+                    let current = next_states[time_to_clay as usize - 1].entry(new_state).or_insert(*geodes);
+                    *current = std::cmp::max(*current, *geodes);
+                    // prev_pos[nt].push((curent_t, id));
+                }
+            }
+
+            if state.obsidian <= max_obsidian_amount {
+                let time_to_obsidian = get_time_to_obsidian(state, blueprint) + 1;
+                let nt = curent_t + (time_to_obsidian as usize);
+
+                if nt <= time {
+                    let mut new_state = advance_state_in_time(state, time_to_obsidian);
+                    new_state.ore -= blueprint.ore_for_obsidian as i16;
+                    new_state.clay -= blueprint.clay_for_obsidian as i16;
+                    new_state.obsidian_robots += 1;
+
+                    let current = next_states[time_to_obsidian as usize - 1].entry(new_state).or_insert(*geodes);
+                    *current = std::cmp::max(*current, *geodes);
+                    // prev_pos[nt].push((curent_t, id));
+                }
+            }
+
+            let time_to_geode = get_time_to_geode(state, blueprint) + 1;
+            let nt = curent_t + (time_to_geode as usize);
+
+            if nt <= time {
+                let mut new_state = advance_state_in_time(state, time_to_geode);
+                new_state.ore -= blueprint.ore_for_geode as i16;
+                new_state.obsidian -= blueprint.obsidian_for_geode as i16;
+
+                let time_left = time - nt;
+
+                // println!("{}->{}: +{}\n{:?}", curent_t, nt, time_left, state);
+
+                let geodes = geodes + time_left as i16;
+
+                best = std::cmp::max(best, geodes);
+                let current = next_states[time_to_geode as usize - 1].entry(new_state).or_insert(geodes);
+                *current = std::cmp::max(*current, geodes);
+                // prev_pos[nt].push((curent_t, id));
+            }
+        }
+
+        left.last_mut().unwrap().clear();
+    }
+
+    best
+}
+
+/// Part 1 sums the quality levels of all blueprints, part 2 multiplies the
+/// geodes of the first three in 32 minutes.
+pub struct Day19;
+
+impl Solution for Day19 {
+    // Sum of the quality levels of all blueprints
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        let blueprints = aoc::parse::lines(input, parse_blueprint)?;
+
+        let p1: i32 = blueprints.iter()
+            .enumerate()
+            .map(|(id, blueprint)| {
+                let answer = get_answer(blueprint, 24) as i32;
+                aoc::progress!("blueprint {}: {}", id + 1, answer);
+                (id + 1) as i32 * answer
+            })
+            .sum();
+
+        Ok(p1.to_string())
+    }
+
+    // Product of the geodes the first three blueprints can open in 32 minutes
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        let blueprints = aoc::parse::lines(input, parse_blueprint)?;
+
+        let p2: i32 = blueprints.iter()
+            .take(3)
+            .enumerate()
+            .map(|(id, blueprint)| {
+                let answer = get_answer(blueprint, 32) as i32;
+                aoc::progress!("blueprint {}: {}", id + 1, answer);
+                answer
+            })
+            .product();
+
+        Ok(p2.to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate::generate(rng, size)
+    }
+}
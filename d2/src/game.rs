@@ -0,0 +1,394 @@
+//! Games like Rock Paper Scissors, described by a small config file. See
+//! `games/rps.txt` for the format.
+
+use aoc::{Cursor, ParseError};
+use std::ops::Range;
+use std::str::FromStr;
+
+/// A move, as its position in the game's list of moves.
+pub type Hand = usize;
+
+/// How a round ends for one of the players.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Lose,
+    Draw,
+    Win,
+}
+
+impl Outcome {
+    /// What the outcome is worth, on top of the hand played.
+    pub fn score(self) -> i32 {
+        match self {
+            Outcome::Lose => 0,
+            Outcome::Draw => 3,
+            Outcome::Win => 6,
+        }
+    }
+}
+
+/// A game in which every pair of moves has exactly one winner and every move
+/// beats as many moves as it loses to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    names: Vec<String>,
+    scores: Vec<i32>,
+    his: Vec<char>,
+    mine: Vec<char>,
+    /// `beats[a][b]` when `a` beats `b`
+    beats: Vec<Vec<bool>>,
+    /// My letters read as outcomes, each with the outcome and which of the
+    /// moves reaching it, in order, it picks
+    aims: Vec<(char, Outcome, usize)>,
+}
+
+impl Game {
+    /// Rock Paper Scissors, as in the puzzle.
+    pub fn classic() -> Game {
+        include_str!("../games/rps.txt").parse().expect("games/rps.txt is a valid game")
+    }
+
+    pub fn hands(&self) -> Range<Hand> {
+        0..self.names.len()
+    }
+
+    pub fn name(&self, hand: Hand) -> &str {
+        &self.names[hand]
+    }
+
+    pub fn beats(&self, hand: Hand, other: Hand) -> bool {
+        self.beats[hand][other]
+    }
+
+    /// How the round ends for whoever plays `hand`.
+    pub fn outcome(&self, hand: Hand, other: Hand) -> Outcome {
+        if self.beats(hand, other) {
+            Outcome::Win
+        } else if self.beats(other, hand) {
+            Outcome::Lose
+        } else {
+            Outcome::Draw
+        }
+    }
+
+    /// 6 if `p1` beats `p2`, 3 for a draw and 0 if `p1` loses.
+    pub fn score_game(&self, p1: Hand, p2: Hand) -> i32 {
+        self.outcome(p1, p2).score()
+    }
+
+    /// What playing `hand` is worth, win or lose.
+    pub fn score_hand(&self, hand: Hand) -> i32 {
+        self.scores[hand]
+    }
+
+    /// The letters of his column of the guide, in the order of the moves.
+    pub fn his_letters(&self) -> String {
+        self.his.iter().collect()
+    }
+
+    /// The letters of my column of the guide, in the order of the moves.
+    pub fn my_letters(&self) -> String {
+        self.mine.iter().collect()
+    }
+
+    pub fn his_hand(&self, letter: char) -> Option<Hand> {
+        self.his.iter().position(|&c| c == letter)
+    }
+
+    pub fn my_hand(&self, letter: char) -> Option<Hand> {
+        self.mine.iter().position(|&c| c == letter)
+    }
+
+    /// The hand that gets the outcome `letter` of my column stands for
+    /// against `his_hand`.
+    pub fn aim(&self, his_hand: Hand, letter: char) -> Option<Hand> {
+        let &(_, outcome, nth) = self.aims.iter().find(|(c, _, _)| *c == letter)?;
+        self.hands().filter(|&hand| self.outcome(hand, his_hand) == outcome).nth(nth)
+    }
+
+    fn hand(&self, name: &str) -> Option<Hand> {
+        self.names.iter().position(|n| n == name)
+    }
+
+    /// How many moves each move beats, and loses to.
+    fn half(&self) -> usize {
+        self.names.len() / 2
+    }
+
+    fn parse_line(&mut self, cursor: &mut Cursor) -> Result<(), ParseError> {
+        let start = cursor.clone();
+        let key = cursor.word()?;
+        if self.names.is_empty() && key != "moves" {
+            return Err(start.error("\"moves\" before anything else"));
+        }
+        let line = cursor.line();
+        let count = |expected: usize, what: &str, found: usize| {
+            ParseError::new(line, 1, format!("{} {}", expected, what), found.to_string())
+        };
+        let once = |done: bool| if done { Err(start.error("each setting once")) } else { Ok(()) };
+
+        match key {
+            "moves" => {
+                once(!self.names.is_empty())?;
+                self.names = list(cursor, "a move not listed yet", true, |c| Ok(c.word()?.to_string()))?;
+                let n = self.names.len();
+                if n < 3 || n.is_multiple_of(2) {
+                    return Err(ParseError::new(line, 1, "an odd number of moves, at least 3", n.to_string()));
+                }
+                self.beats = vec![vec![false; n]; n];
+            }
+            "scores" => {
+                once(!self.scores.is_empty())?;
+                self.scores = list(cursor, "a score", false, Cursor::number)?;
+                if self.scores.len() != self.names.len() {
+                    return Err(count(self.names.len(), "scores", self.scores.len()));
+                }
+            }
+            "his" | "mine" => {
+                let letters = list(cursor, "a letter not listed yet", true, letter)?;
+                if letters.len() != self.names.len() {
+                    return Err(count(self.names.len(), "letters", letters.len()));
+                }
+                let column = if key == "his" { &mut self.his } else { &mut self.mine };
+                once(!column.is_empty())?;
+                *column = letters;
+            }
+            "lose" | "draw" | "win" => {
+                let outcome = match key {
+                    "lose" => Outcome::Lose,
+                    "draw" => Outcome::Draw,
+                    _ => Outcome::Win,
+                };
+                once(self.aims.iter().any(|&(_, o, _)| o == outcome))?;
+                let aims = &self.aims;
+                let letters = list(cursor, "a letter not listed yet", true, |c| {
+                    let at = c.clone();
+                    match letter(c)? {
+                        l if aims.iter().any(|&(a, _, _)| a == l) => Err(at.error("a letter not used for another outcome")),
+                        l => Ok(l),
+                    }
+                })?;
+                let expected = if outcome == Outcome::Draw { 1 } else { self.half() };
+                if letters.len() != expected {
+                    let what = if expected == 1 { "letter" } else { "letters" };
+                    return Err(count(expected, &format!("{} to {}", what, key), letters.len()));
+                }
+                self.aims.extend(letters.into_iter().enumerate().map(|(nth, l)| (l, outcome, nth)));
+            }
+            _ => {
+                let hand = self.hand(key).ok_or_else(|| start.error("one of the moves or a setting"))?;
+                cursor.tag(" beats")?;
+                let losers = list(cursor, "a move not listed yet", true, |c| {
+                    let at = c.clone();
+                    let other = self.hand(c.word()?).ok_or_else(|| at.error("one of the moves"))?;
+                    if other == hand {
+                        Err(at.error(format!("a move other than {}", key)))
+                    } else if self.beats(other, hand) {
+                        Err(at.error(format!("a move that doesn't beat {}", key)))
+                    } else {
+                        Ok(other)
+                    }
+                })?;
+                for other in losers {
+                    self.beats[hand][other] = true;
+                }
+            }
+        }
+
+        cursor.end()
+    }
+
+    /// Checks what can only be checked once the whole game is known.
+    fn check(&self, input: &str) -> Result<(), ParseError> {
+        let missing = |expected: String, found: String| ParseError::new(input.lines().count() + 1, 1, expected, found);
+
+        let settings = [
+            ("moves", self.names.is_empty()),
+            ("scores", self.scores.is_empty()),
+            ("his", self.his.is_empty()),
+            ("mine", self.mine.is_empty()),
+        ];
+        for (key, is_missing) in settings {
+            if is_missing {
+                return Err(ParseError::end_of_input(input, format!("a {} line", key)));
+            }
+        }
+        for (key, outcome) in [("lose", Outcome::Lose), ("draw", Outcome::Draw), ("win", Outcome::Win)] {
+            if !self.aims.iter().any(|&(_, o, _)| o == outcome) {
+                return Err(ParseError::end_of_input(input, format!("a {} line", key)));
+            }
+        }
+        if let Some(&(letter, _, _)) = self.aims.iter().find(|(l, _, _)| !self.mine.contains(l)) {
+            return Err(missing("outcomes among the letters of mine".into(), format!("{:?}", letter)));
+        }
+
+        for hand in self.hands() {
+            for other in hand + 1..self.names.len() {
+                if !self.beats(hand, other) && !self.beats(other, hand) {
+                    let expected = format!("a line saying whether {} beats {}", self.name(hand), self.name(other));
+                    return Err(ParseError::end_of_input(input, expected));
+                }
+            }
+        }
+        for hand in self.hands() {
+            let beaten = self.hands().filter(|&other| self.beats(hand, other)).count();
+            if beaten != self.half() {
+                let expected = format!("{} to beat half of the other moves", self.name(hand));
+                return Err(missing(expected, format!("{} beating {}", self.name(hand), beaten)));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Game {
+    type Err = ParseError;
+
+    /// Reads a game, one setting or beats line per line. Blank lines and
+    /// lines starting with `#` are skipped.
+    fn from_str(input: &str) -> Result<Game, ParseError> {
+        let mut game = Game {
+            names: Vec::new(),
+            scores: Vec::new(),
+            his: Vec::new(),
+            mine: Vec::new(),
+            beats: Vec::new(),
+            aims: Vec::new(),
+        };
+
+        for mut cursor in aoc::parse::cursors(input) {
+            cursor.skip_whitespace();
+            if !cursor.is_empty() && cursor.peek() != Some('#') {
+                game.parse_line(&mut cursor)?;
+            }
+        }
+
+        game.check(input)?;
+        Ok(game)
+    }
+}
+
+/// Reads the items up to the end of the line, separated by whitespace. With
+/// `distinct` none of them may be listed twice.
+fn list<'a, T: PartialEq>(
+    cursor: &mut Cursor<'a>,
+    what: &str,
+    distinct: bool,
+    mut item: impl FnMut(&mut Cursor<'a>) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    let mut items = Vec::new();
+    loop {
+        cursor.skip_whitespace();
+        if cursor.is_empty() {
+            break;
+        }
+        let at = cursor.clone();
+        let value = item(cursor)?;
+        if distinct && items.contains(&value) {
+            return Err(at.error(what));
+        }
+        items.push(value);
+    }
+
+    if items.is_empty() {
+        return Err(cursor.error(what));
+    }
+    Ok(items)
+}
+
+fn letter(cursor: &mut Cursor) -> Result<char, ParseError> {
+    let at = cursor.clone();
+    let letter = cursor.next_char()?;
+    match cursor.peek() {
+        Some(c) if !c.is_whitespace() => Err(at.error("a single letter")),
+        _ => Ok(letter),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rpsls() -> Game {
+        include_str!("../games/rpsls.txt").parse().unwrap()
+    }
+
+    #[test]
+    fn test_classic() {
+        let game = Game::classic();
+        let [rock, paper, scissors] = [0, 1, 2];
+        assert_eq!(game.name(scissors), "Scissors");
+        assert_eq!(game.score_game(rock, scissors), 6);
+        assert_eq!(game.score_game(rock, paper), 0);
+        assert_eq!(game.score_game(paper, paper), 3);
+        assert_eq!(game.score_hand(scissors), 3);
+        assert_eq!(game.his_letters(), "ABC");
+        assert_eq!(game.my_hand('Y'), Some(paper));
+        assert_eq!(game.aim(rock, 'X'), Some(scissors));
+        assert_eq!(game.aim(paper, 'Y'), Some(paper));
+        assert_eq!(game.aim(scissors, 'Z'), Some(rock));
+    }
+
+    #[test]
+    fn test_rpsls() {
+        let game = rpsls();
+        let hand = |name| game.hand(name).unwrap();
+        assert!(game.beats(hand("Spock"), hand("Rock")));
+        assert_eq!(game.outcome(hand("Lizard"), hand("Rock")), Outcome::Lose);
+        assert_eq!(game.aim(hand("Rock"), 'V'), Some(hand("Scissors")));
+        assert_eq!(game.aim(hand("Rock"), 'W'), Some(hand("Lizard")));
+        assert_eq!(game.aim(hand("Rock"), 'Y'), Some(hand("Paper")));
+        assert_eq!(game.aim(hand("Rock"), 'Z'), Some(hand("Spock")));
+        assert_eq!(game.aim(hand("Rock"), 'A'), None);
+    }
+
+    #[test]
+    fn test_invalid() {
+        let classic = include_str!("../games/rps.txt");
+        let game = |changed: &str, with: &str| classic.replace(changed, with).parse::<Game>();
+        let lines = classic.lines().count();
+
+        assert_eq!(
+            game("moves Rock Paper Scissors", "moves Rock Paper Rock"),
+            Err(ParseError::new(7, 18, "a move not listed yet", "\"Rock\""))
+        );
+        assert_eq!(
+            game("moves Rock Paper Scissors", "moves Rock Paper"),
+            Err(ParseError::new(7, 1, "an odd number of moves, at least 3", "2"))
+        );
+        assert_eq!(game("scores 1 2 3", "scores 1 2"), Err(ParseError::new(8, 1, "3 scores", "2")));
+        assert_eq!(
+            game("his A B C", "his A BC"),
+            Err(ParseError::new(9, 7, "a single letter", "\"BC\""))
+        );
+        assert_eq!(
+            game("win Z", "win X"),
+            Err(ParseError::new(13, 5, "a letter not used for another outcome", "\"X\""))
+        );
+        assert_eq!(
+            game("Paper beats Rock", "Paper beats Paper"),
+            Err(ParseError::new(15, 13, "a move other than Paper", "\"Paper\""))
+        );
+        assert_eq!(
+            game("Paper beats Rock", "Paper beats Scissors"),
+            Err(ParseError::new(16, 16, "a move that doesn't beat Scissors", "\"Paper\""))
+        );
+        assert_eq!(
+            game("Paper beats Rock", ""),
+            Err(ParseError::new(lines + 1, 1, "a line saying whether Rock beats Paper", "end of input"))
+        );
+        assert_eq!(
+            game("Paper beats Rock", "Rock beats Paper"),
+            Err(ParseError::new(lines + 1, 1, "Rock to beat half of the other moves", "Rock beating 2"))
+        );
+        assert_eq!(
+            game("mine X Y Z", "mine X Y W"),
+            Err(ParseError::new(lines + 1, 1, "outcomes among the letters of mine", "'Z'"))
+        );
+        assert_eq!(
+            "scores 1 2 3".parse::<Game>(),
+            Err(ParseError::new(1, 1, "\"moves\" before anything else", "\"scores\""))
+        );
+    }
+}
@@ -0,0 +1,8 @@
+use aoc::Rng;
+
+/// A strategy guide of `size` rounds.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| format!("{} {}\n", rng.pick(&['A', 'B', 'C']), rng.pick(&['X', 'Y', 'Z'])))
+        .collect()
+}
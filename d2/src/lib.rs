@@ -0,0 +1,179 @@
+//! Day 2: Rock Paper Scissors. The strategy guide is scored under two
+//! readings of its second column, for the puzzle's game or any other [`Game`]
+//! such as Rock Paper Scissors Lizard Spock.
+
+use aoc::{Cursor, ParseError, Rng, Solution};
+use std::fmt;
+
+pub mod game;
+mod generate;
+pub mod strategy;
+
+pub use game::{Game, Hand, Outcome};
+pub use strategy::{analyse, Analysis, Equilibrium};
+
+/// The two columns of a line of the strategy guide, in the letters of `game`.
+pub fn parse_line(game: &Game, cursor: &mut Cursor) -> Result<(char, char), ParseError> {
+    let his_move = cursor.one_of(&game.his_letters())?;
+    cursor.tag(" ")?;
+    let my_move = cursor.one_of(&game.my_letters())?;
+    Ok((his_move, my_move))
+}
+
+/// How to read the second column of the guide.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interpretation {
+    /// X, Y and Z are the hands to play: rock, paper or scissors
+    Moves,
+    /// X, Y and Z are the outcomes to aim for: lose, draw or win
+    Outcomes,
+}
+
+impl Interpretation {
+    /// The hand to play against `his_hand` when the guide says `column`,
+    /// which has to be one of the letters of my column.
+    pub fn my_hand(self, game: &Game, his_hand: Hand, column: char) -> Hand {
+        let hand = match self {
+            Interpretation::Moves => game.my_hand(column),
+            Interpretation::Outcomes => game.aim(his_hand, column),
+        };
+        hand.expect("a letter of my column")
+    }
+
+    /// What I play in a round of the guide and what it scores.
+    pub fn play(self, game: &Game, (his_move, column): &(char, char)) -> Play {
+        let his_hand = game.his_hand(*his_move).expect("a letter of his column");
+        let hand = self.my_hand(game, his_hand, *column);
+        let score = game.score_game(hand, his_hand) + game.score_hand(hand);
+        Play { his_hand, hand, score }
+    }
+}
+
+/// A round as played under one interpretation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Play {
+    pub his_hand: Hand,
+    pub hand: Hand,
+    pub score: i32,
+}
+
+/// A line of the guide played under both interpretations.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Round<'g> {
+    pub game: &'g Game,
+    pub line: usize,
+    pub guide: (char, char),
+    pub moves: Play,
+    pub outcomes: Play,
+}
+
+impl fmt::Display for Round<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let play = |play: &Play| {
+            let (hand, his_hand) = (self.game.name(play.hand), self.game.name(play.his_hand));
+            format!("{} vs {}: {}", hand, his_hand, play.score)
+        };
+        write!(
+            f,
+            "{:5} {} {}  moves {:24} outcomes {}",
+            self.line,
+            self.guide.0,
+            self.guide.1,
+            play(&self.moves),
+            play(&self.outcomes)
+        )
+    }
+}
+
+/// The total score of the guide under each interpretation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Totals {
+    pub moves: i32,
+    pub outcomes: i32,
+}
+
+/// Plays the guide of `game` under both interpretations in a single pass.
+/// Every round is handed to `on_round` as well, for a breakdown.
+pub fn score_guide<'g>(
+    game: &'g Game,
+    input: &str,
+    mut on_round: impl FnMut(&Round<'g>),
+) -> Result<Totals, ParseError> {
+    let mut totals = Totals { moves: 0, outcomes: 0 };
+
+    for mut cursor in aoc::parse::cursors(input) {
+        let guide = parse_line(game, &mut cursor)?;
+        cursor.end()?;
+
+        let round = Round {
+            game,
+            line: cursor.line(),
+            guide,
+            moves: Interpretation::Moves.play(game, &guide),
+            outcomes: Interpretation::Outcomes.play(game, &guide),
+        };
+        totals.moves += round.moves.score;
+        totals.outcomes += round.outcomes.score;
+        on_round(&round);
+    }
+
+    Ok(totals)
+}
+
+/// Part 1 reads the second column as moves, part 2 as outcomes.
+pub struct Day2;
+
+impl Solution for Day2 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        Ok(score_guide(&Game::classic(), input, |_| ())?.moves.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        Ok(score_guide(&Game::classic(), input, |_| ())?.outcomes.to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate::generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interpretations() {
+        let game = Game::classic();
+        let [rock, paper, scissors] = [0, 1, 2];
+        assert_eq!(Interpretation::Moves.my_hand(&game, rock, 'Y'), paper);
+        assert_eq!(Interpretation::Outcomes.my_hand(&game, rock, 'Y'), rock);
+        assert_eq!(Interpretation::Outcomes.my_hand(&game, paper, 'X'), rock);
+        assert_eq!(Interpretation::Outcomes.my_hand(&game, scissors, 'Z'), rock);
+
+        let play = Interpretation::Moves.play(&game, &('C', 'Z'));
+        assert_eq!(play, Play { his_hand: scissors, hand: scissors, score: 6 });
+    }
+
+    #[test]
+    fn test_score_guide() {
+        let game = Game::classic();
+        let mut rounds = Vec::new();
+        let totals = score_guide(&game, include_str!("../sample.txt"), |round| rounds.push(round.clone())).unwrap();
+        assert_eq!(totals, Totals { moves: 15, outcomes: 12 });
+
+        assert_eq!(rounds.len(), 3);
+        assert_eq!(rounds[0].to_string(), "    1 A Y  moves Paper vs Rock: 8         outcomes Rock vs Rock: 4");
+        assert_eq!(rounds[2].line, 3);
+    }
+
+    #[test]
+    fn test_rpsls_guide() {
+        let game: Game = include_str!("../games/rpsls.txt").parse().unwrap();
+        let mut rounds = Vec::new();
+        let totals = score_guide(&game, "A V\nE Z\nC X\n", |round| rounds.push(round.to_string())).unwrap();
+        assert_eq!(rounds[0], "    1 A V  moves Rock vs Rock: 4          outcomes Scissors vs Rock: 3");
+        assert_eq!(rounds[1], "    2 E Z  moves Spock vs Spock: 8        outcomes Lizard vs Spock: 10");
+        assert_eq!(totals, Totals { moves: 4 + 8 + 6, outcomes: 3 + 10 + 6 });
+        assert!(score_guide(&game, "A U", |_| ()).is_err());
+    }
+}
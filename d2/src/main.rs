@@ -1,89 +1,58 @@
-use std::convert::TryFrom;
-use std::convert::TryInto;
-use std::io::{self, BufRead};
+use aoc::cli::{self, Format, Options};
+use d2::{Day2, Game};
 
-#[derive(PartialEq, Debug, Clone)]
-enum RockPaperScissors {
-    Rock,
-    Paper,
-    Scissors,
-}
-
-impl TryFrom<i32> for RockPaperScissors {
-    type Error = ();
+const USAGE: &str = "d2 also takes --rounds to show the score of every round under both readings of the guide,
+--analyse to compare the guide with the best answers to his moves and with the equilibrium mix,
+and --game <PATH> to play the guide as another game, such as d2/games/rpsls.txt";
 
-    fn try_from(v: i32) -> Result<Self, Self::Error> {
-        use RockPaperScissors::*;
-        match v {
-            x if x == Rock as i32 => Ok(Rock),
-            x if x == Paper as i32 => Ok(Paper),
-            x if x == Scissors as i32 => Ok(Scissors),
-            _ => Err(()),
+fn main() {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let rounds = args.iter().position(|arg| arg == "--rounds").map(|i| args.remove(i));
+    let analyse = args.iter().position(|arg| arg == "--analyse").map(|i| args.remove(i));
+    let game = match args.iter().position(|arg| arg == "--game") {
+        Some(i) => {
+            let Some(path) = args.get(i + 1).cloned() else {
+                eprintln!("--game needs a path\n\n{}", USAGE);
+                std::process::exit(2);
+            };
+            args.drain(i..i + 2);
+            Some(path)
         }
-    }
-}
-
-#[test]
-fn test_try_from() {
-    assert_eq!(RockPaperScissors::try_from(0), Ok(RockPaperScissors::Rock));
-    assert_eq!(RockPaperScissors::try_from(1), Ok(RockPaperScissors::Paper));
-    assert_eq!(
-        RockPaperScissors::try_from(2),
-        Ok(RockPaperScissors::Scissors)
-    );
-    assert_eq!(RockPaperScissors::try_from(3), Err(()));
-}
+        None => None,
+    };
 
-fn score_game(p1: &RockPaperScissors, p2: &RockPaperScissors) -> i32 {
-    use RockPaperScissors::*;
-    match (p1, p2) {
-        (Rock, Scissors) | (Paper, Rock) | (Scissors, Paper) => 6,
-        (Scissors, Rock) | (Rock, Paper) | (Paper, Scissors) => 0,
-        _ => 3,
+    let options = Options::from_args(args);
+    if rounds.is_none() && analyse.is_none() && game.is_none() {
+        cli::run(2, &Day2, env!("CARGO_MANIFEST_DIR"), &options);
+        return;
     }
-}
 
-fn score_hand(hand: &RockPaperScissors) -> i32 {
-    use RockPaperScissors::*;
-    match hand {
-        Rock => 1,
-        Paper => 2,
-        Scissors => 3,
+    if options.format != Format::Text {
+        eprintln!("--rounds, --analyse and --game only make a text breakdown\n\n{}", USAGE);
+        std::process::exit(2);
     }
-}
-
-fn score(line: &str) -> i32 {
-    // split line on space
-    let his_move = line.chars().nth(0).unwrap() as i32;
-    let my_move = line.chars().nth(2).unwrap();
-
-    let his_move: RockPaperScissors = (his_move - 'A' as i32).try_into().unwrap();
-
-    use RockPaperScissors::*;
-
-    let my_move = match (&his_move, my_move) {
-        (_, 'Y') => his_move.clone(),
-        (Rock, 'X') => Scissors,
-        (Paper, 'X') => Rock,
-        (Scissors, 'X') => Paper,
-        (Rock, 'Z') => Paper,
-        (Paper, 'Z') => Scissors,
-        (Scissors, 'Z') => Rock,
-        (_, _) => panic!("Invalid input"),
+    let game = match game {
+        Some(path) => {
+            let text = std::fs::read_to_string(&path).unwrap_or_else(|err| {
+                eprintln!("Can't read {}: {}", path, err);
+                std::process::exit(1);
+            });
+            text.parse::<Game>().unwrap_or_else(|err| cli::fail(&err, &path, &text))
+        }
+        None => Game::classic(),
     };
 
-    // print debug string
-    println!("{:?} vs {:?}", his_move, my_move);
-    score_game(&my_move, &his_move) + score_hand(&my_move)
-}
-
-fn main() {
-    // iterate over stdin lines
-    let stdin = io::stdin();
-    let lines = stdin.lock().lines();
-
-    // map lines into score and sum
-    let total_score = lines.map(|line| score(&line.unwrap())).sum::<i32>();
-
-    println!("{}", total_score);
+    let (source, input) = cli::read_input(env!("CARGO_MANIFEST_DIR"), &options);
+    let on_round = |round: &d2::Round| {
+        if rounds.is_some() {
+            println!("{}", round);
+        }
+    };
+    let totals = d2::score_guide(&game, &input, on_round).unwrap_or_else(|err| cli::fail(&err, &source, &input));
+    if analyse.is_none() {
+        println!("Total  moves {}  outcomes {}", totals.moves, totals.outcomes);
+        return;
+    }
+    let analysis = d2::analyse(&game, &input).unwrap_or_else(|err| cli::fail(&err, &source, &input));
+    print!("{}", analysis);
 }
@@ -0,0 +1,267 @@
+//! What the guide could have scored: the best answer to each of his moves,
+//! what a mix of moves is worth and the mix he can't exploit.
+
+use crate::{score_guide, Game, Hand, Totals};
+use aoc::ParseError;
+use std::fmt;
+
+const EPSILON: f64 = 1e-9;
+
+/// What every hand of mine scores against every hand of his, as
+/// `table[mine][his]`.
+pub fn scoring_table(game: &Game) -> Vec<Vec<i32>> {
+    game.hands()
+        .map(|hand| game.hands().map(|his_hand| game.score_game(hand, his_hand) + game.score_hand(hand)).collect())
+        .collect()
+}
+
+/// The hand that scores most against each of his hands, with its score. Of
+/// equally good hands the first one is taken.
+pub fn best_responses(table: &[Vec<i32>]) -> Vec<(Hand, i32)> {
+    (0..table[0].len())
+        .map(|his_hand| {
+            let mut best = (0, table[0][his_hand]);
+            for (hand, row) in table.iter().enumerate().skip(1) {
+                if row[his_hand] > best.1 {
+                    best = (hand, row[his_hand]);
+                }
+            }
+            best
+        })
+        .collect()
+}
+
+/// The score I can expect per round when both of us pick our hands at random
+/// with the chances in `mine` and `his`.
+pub fn expected_score(table: &[Vec<i32>], mine: &[f64], his: &[f64]) -> f64 {
+    table
+        .iter()
+        .zip(mine)
+        .map(|(row, p)| p * row.iter().zip(his).map(|(&score, q)| score as f64 * q).sum::<f64>())
+        .sum()
+}
+
+/// A pair of mixes neither of us gains by leaving, when he plays to keep my
+/// score down.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Equilibrium {
+    /// The chance of playing each of my hands
+    pub mine: Vec<f64>,
+    /// The chance of each of his hands
+    pub his: Vec<f64>,
+    /// What `mine` scores per round at least, whatever he plays
+    pub value: f64,
+}
+
+/// Solves the game of `table` as a zero-sum game with the simplex method.
+///
+/// The table is shifted so that every score is positive. His mix then comes
+/// from maximising the sum of `y` with `table · y ≤ 1` and `y ≥ 0`, and
+/// mine from the dual of that, which ends up under the slack variables of
+/// the objective row.
+pub fn equilibrium(table: &[Vec<i32>]) -> Equilibrium {
+    let (rows, columns) = (table.len(), table[0].len());
+    let shift = 1 - table.iter().flatten().min().unwrap();
+    let width = columns + rows + 1;
+
+    let mut tableau: Vec<Vec<f64>> = table
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let mut line = vec![0.0; width];
+            for (cell, &score) in line.iter_mut().zip(row) {
+                *cell = (score + shift) as f64;
+            }
+            line[columns + i] = 1.0;
+            line[width - 1] = 1.0;
+            line
+        })
+        .collect();
+    let mut objective = vec![0.0; width];
+    objective[..columns].fill(-1.0);
+    let mut basis: Vec<usize> = (columns..columns + rows).collect();
+
+    // Bland's rule, the first column that improves and the first variable of
+    // the tied rows, so the pivots can't cycle
+    while let Some(enter) = (0..width - 1).find(|&j| objective[j] < -EPSILON) {
+        let ratio = |i: usize| tableau[i][width - 1] / tableau[i][enter];
+        let leave = (0..rows)
+            .filter(|&i| tableau[i][enter] > EPSILON)
+            .min_by(|&a, &b| ratio(a).total_cmp(&ratio(b)).then(basis[a].cmp(&basis[b])))
+            .expect("a bounded problem, as every score is positive");
+
+        let pivot = tableau[leave][enter];
+        tableau[leave].iter_mut().for_each(|cell| *cell /= pivot);
+        let pivot_row = tableau[leave].clone();
+        let eliminate = |line: &mut Vec<f64>| {
+            let factor = line[enter];
+            line.iter_mut().zip(&pivot_row).for_each(|(cell, p)| *cell -= factor * p);
+        };
+        tableau.iter_mut().enumerate().filter(|(i, _)| *i != leave).for_each(|(_, line)| eliminate(line));
+        eliminate(&mut objective);
+        basis[leave] = enter;
+    }
+
+    let value = 1.0 / objective[width - 1];
+    let mut his = vec![0.0; columns];
+    for (line, &variable) in tableau.iter().zip(&basis) {
+        if variable < columns {
+            his[variable] = line[width - 1] * value;
+        }
+    }
+    let mine = objective[columns..columns + rows].iter().map(|x| x * value).collect();
+
+    Equilibrium {
+        mine,
+        his,
+        value: value - shift as f64,
+    }
+}
+
+/// How the guide of a game compares with playing it well.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Analysis<'g> {
+    pub game: &'g Game,
+    pub table: Vec<Vec<i32>>,
+    /// How often he plays each hand
+    pub his_counts: Vec<usize>,
+    /// How often I play each hand when the guide is read as moves
+    pub my_counts: Vec<usize>,
+    /// What the guide scores under both interpretations
+    pub totals: Totals,
+    /// The best answer to each of his hands and its score
+    pub best: Vec<(Hand, i32)>,
+    pub equilibrium: Equilibrium,
+}
+
+impl Analysis<'_> {
+    /// What the guide would score with the best answer in every round.
+    pub fn optimal(&self) -> i32 {
+        self.his_counts.iter().zip(&self.best).map(|(&count, &(_, score))| count as i32 * score).sum()
+    }
+
+    /// How often he plays each hand, as chances.
+    pub fn his_mix(&self) -> Vec<f64> {
+        mix(&self.his_counts)
+    }
+
+    /// How often I play each hand when the guide is read as moves, as chances.
+    pub fn my_mix(&self) -> Vec<f64> {
+        mix(&self.my_counts)
+    }
+}
+
+fn mix(counts: &[usize]) -> Vec<f64> {
+    let rounds: usize = counts.iter().sum();
+    counts.iter().map(|&count| count as f64 / rounds.max(1) as f64).collect()
+}
+
+/// Plays the guide of `game` and works out what it could have scored.
+pub fn analyse<'g>(game: &'g Game, input: &str) -> Result<Analysis<'g>, ParseError> {
+    let mut his_counts = vec![0; game.hands().len()];
+    let mut my_counts = vec![0; game.hands().len()];
+    let totals = score_guide(game, input, |round| {
+        his_counts[round.moves.his_hand] += 1;
+        my_counts[round.moves.hand] += 1;
+    })?;
+
+    let table = scoring_table(game);
+    Ok(Analysis {
+        game,
+        best: best_responses(&table),
+        equilibrium: equilibrium(&table),
+        table,
+        his_counts,
+        my_counts,
+        totals,
+    })
+}
+
+impl fmt::Display for Analysis<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = |hand| self.game.name(hand);
+        let width = self.game.hands().map(|hand| name(hand).len()).max().unwrap_or(0);
+
+        writeln!(f, "His moves and the best answer to each:")?;
+        for (his_hand, &(hand, score)) in self.best.iter().enumerate() {
+            let count = self.his_counts[his_hand];
+            writeln!(f, "  {:width$} {:6} times, {} for {}", name(his_hand), count, name(hand), score)?;
+        }
+
+        let optimal = self.optimal();
+        writeln!(f, "Best answers  {}", optimal)?;
+        writeln!(f, "Moves         {}  ({} short)", self.totals.moves, optimal - self.totals.moves)?;
+        writeln!(f, "Outcomes      {}  ({} short)", self.totals.outcomes, optimal - self.totals.outcomes)?;
+
+        let his_mix = self.his_mix();
+        let per_round = |mine: &[f64]| expected_score(&self.table, mine, &his_mix);
+        writeln!(
+            f,
+            "Per round against his mix: moves {:.2}, equilibrium {:.2}",
+            per_round(&self.my_mix()),
+            per_round(&self.equilibrium.mine)
+        )?;
+
+        let mix: Vec<String> = self.equilibrium.mine.iter().enumerate()
+            .map(|(hand, p)| format!("{} {:.3}", name(hand), p))
+            .collect();
+        writeln!(f, "Equilibrium: {}, worth {:.2} a round whatever he plays", mix.join(", "), self.equilibrium.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks that neither of us can do better by leaving the equilibrium.
+    fn assert_equilibrium(table: &[Vec<i32>], equilibrium: &Equilibrium) {
+        assert!((equilibrium.mine.iter().sum::<f64>() - 1.0).abs() < 1e-6);
+        assert!((equilibrium.his.iter().sum::<f64>() - 1.0).abs() < 1e-6);
+        for his_hand in 0..table[0].len() {
+            let pure: Vec<f64> = (0..table[0].len()).map(|h| if h == his_hand { 1.0 } else { 0.0 }).collect();
+            assert!(expected_score(table, &equilibrium.mine, &pure) >= equilibrium.value - 1e-6);
+        }
+        for hand in 0..table.len() {
+            let pure: Vec<f64> = (0..table.len()).map(|h| if h == hand { 1.0 } else { 0.0 }).collect();
+            assert!(expected_score(table, &pure, &equilibrium.his) <= equilibrium.value + 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_best_responses() {
+        let table = scoring_table(&Game::classic());
+        assert_eq!(table, vec![vec![4, 1, 7], vec![8, 5, 2], vec![3, 9, 6]]);
+        assert_eq!(best_responses(&table), vec![(1, 8), (2, 9), (0, 7)]);
+        assert_eq!(expected_score(&table, &[0.0, 1.0, 0.0], &[0.5, 0.0, 0.5]), 5.0);
+    }
+
+    #[test]
+    fn test_equilibrium() {
+        let fair = vec![vec![0, -1, 1], vec![1, 0, -1], vec![-1, 1, 0]];
+        let equilibrium = equilibrium(&fair);
+        assert!(equilibrium.value.abs() < 1e-9);
+        assert!(equilibrium.mine.iter().chain(&equilibrium.his).all(|p| (p - 1.0 / 3.0).abs() < 1e-9));
+
+        let classic = scoring_table(&Game::classic());
+        assert_equilibrium(&classic, &super::equilibrium(&classic));
+
+        let rpsls = scoring_table(&include_str!("../games/rpsls.txt").parse().unwrap());
+        assert_equilibrium(&rpsls, &super::equilibrium(&rpsls));
+
+        // Paper never loses here, so it's all I play
+        let lopsided = vec![vec![1, 0], vec![2, 3]];
+        let equilibrium = super::equilibrium(&lopsided);
+        assert_eq!((equilibrium.mine, equilibrium.value), (vec![0.0, 1.0], 2.0));
+    }
+
+    #[test]
+    fn test_analyse() {
+        let game = Game::classic();
+        let analysis = analyse(&game, include_str!("../sample.txt")).unwrap();
+        assert_eq!(analysis.his_counts, vec![1, 1, 1]);
+        assert_eq!(analysis.my_counts, vec![1, 1, 1]);
+        assert_eq!(analysis.totals, Totals { moves: 15, outcomes: 12 });
+        assert_eq!(analysis.optimal(), 24);
+        assert!(analysis.to_string().contains("Moves         15  (9 short)"));
+    }
+}
@@ -0,0 +1,21 @@
+use aoc::Rng;
+
+/// An encrypted file of `size` numbers, at least 3, with a single 0.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let count = size.max(3);
+    let zero = rng.below(count);
+    (0..count)
+        .map(|i| {
+            let number = match i {
+                _ if i == zero => 0,
+                _ => loop {
+                    let number = rng.between(-10000, 10000);
+                    if number != 0 {
+                        break number;
+                    }
+                },
+            };
+            format!("{}\n", number)
+        })
+        .collect()
+}
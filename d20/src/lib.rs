@@ -0,0 +1,203 @@
+//! Day 20: Grove Positioning System. Mixes the encrypted file to find the
+//! grove coordinates.
+
+use aoc::{Cursor, ParseError, Rng, Solution};
+
+mod generate;
+
+/// A number of the encrypted file, one per line.
+pub fn parse_number(cursor: &mut Cursor) -> Result<i64, ParseError> {
+    cursor.number()
+}
+
+fn true_mod(v: i64, m: i32) -> i32 {
+    let rem = (v % (m as i64)) as i32;
+    if rem < 0 {
+        rem + m
+    } else {
+        rem
+    }
+}
+
+fn move_simple(buffer: &mut Vec<i32>, from: i32, to: i32) {
+    let value = buffer[from as usize];
+    buffer.remove(from as usize);
+    buffer.insert(to as usize, value);
+}
+
+fn move_once(buffer: &mut Vec<i32>, pos: i32, value: i64) {
+    let len = buffer.len() as i32;
+    let mut pos = pos;
+
+    let module = (len - 1)*(len - 1);
+    let mut value = true_mod(value, module);
+
+
+    loop {
+        let new_pos = pos + value;
+        if (0..len).contains(&new_pos) {
+            // no wrap arounds, all good
+            move_simple(buffer, pos, new_pos);
+            break;
+        }
+
+        if new_pos < 0 {
+            assert!(value < 0);
+            move_simple(buffer, pos, 0);
+            value += pos;
+            move_simple(buffer, 0, len - 2);
+            value += 1;
+            pos = len - 2;
+        }
+
+        if new_pos >= len {
+            assert!(value > 0);
+            move_simple(buffer, pos, len - 1);
+            value -= len - 1 - pos;
+            move_simple(buffer, len - 1, 1);
+            value -= 1;
+            pos = 1;
+        }
+    }
+}
+
+fn advance(input: &[i64], positions: &mut Vec<i32>, turn: i32) {
+    let pos = positions.iter().position(|&x| x == turn).unwrap();
+    let value = input[positions[pos] as usize];
+    move_once(positions, pos as i32, value);
+}
+
+fn make_answer(positions: &[i32], input: &[i64]) -> Vec<i64> {
+    let mut answer = vec![];
+    for pos in positions {
+        answer.push(input[*pos as usize]);
+    }
+    answer
+}
+
+// #[test]
+// fn test_sample() {
+//     let input = aoc::parse::lines(include_str!("../sample.txt"), parse_number).unwrap();
+
+//     let len = input.len() as i32;
+//     let mut positions: Vec<i32> = (0..len).collect();
+
+//     let states = [
+//         [1, 2, -3, 3, -2, 0, 4],
+//         [2, 1, -3, 3, -2, 0, 4],
+//         [1, -3, 2, 3, -2, 0, 4],
+//         [1, 2, 3, -2, -3, 0, 4],
+//         [1, 2, -2, -3, 0, 3, 4],
+//         [1, 2, -3, 0, 3, 4, -2],
+//         [1, 2, -3, 0, 3, 4, -2],
+//         [1, 2, -3, 4, 0, 3, -2],
+//     ];
+
+
+//     for v in 0..len {
+//         let current = make_answer(&positions, &input);
+
+//         for val in &current {
+//             print!("{}, ", val);
+//         }
+//         println!();
+//         for val in states[v as usize] {
+//             print!("{}, ", val);
+//         }
+//         println!();
+//         println!();
+
+//         assert_eq!(current, states[v as usize]);
+//         advance(&input, &mut positions, v);
+//     }
+// }
+
+/// Mixes the numbers, multiplied by `key`, `rounds` times and returns the sum
+/// of the grove coordinates: the numbers 1000, 2000 and 3000 places after
+/// the 0.
+pub fn grove_coordinates(input: &str, key: i64, rounds: usize) -> Result<i64, ParseError> {
+    let numbers = aoc::parse::lines(input, parse_number)?;
+    if !numbers.contains(&0) {
+        return Err(ParseError::end_of_input(input, "the number 0"));
+    }
+    let input: Vec<i64> = numbers.iter().map(|v| v * key).collect();
+
+    let len = input.len() as i32;
+    let mut positions: Vec<i32> = (0..len).collect();
+
+    for round in 0..rounds {
+        for p in 0..len {
+            if p%100 == 0 {
+                aoc::progress!("{:4}/{} of round {}", p, len, round + 1);
+            }
+            advance(&input, &mut positions, p);
+        }
+    }
+
+    let result = make_answer(&positions, &input);
+    let zero_pos = result.iter().position(|&x| x == 0).unwrap();
+
+    let mut answer = 0;
+    for i in 1..4 {
+        let ipos = true_mod((zero_pos as i32 + i*1000) as i64, len) as usize;
+        aoc::debug!("{}: {}", i * 1000, result[ipos]);
+        answer += result[ipos];
+    }
+
+    Ok(answer)
+}
+
+/// Part 1 mixes once, part 2 ten times after applying the decryption key.
+pub struct Day20;
+
+impl Solution for Day20 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        Ok(grove_coordinates(input, 1, 1)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        Ok(grove_coordinates(input, 811589153, 10)?.to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate::generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc::Rng;
+    use proptest::prelude::*;
+
+    // Mixes the plain way, taking every number out of the list and putting it
+    // back where it belongs
+    fn mix_slowly(numbers: &[i64], key: i64, rounds: usize) -> i64 {
+        let mut list: Vec<(usize, i64)> = numbers.iter().map(|v| v * key).enumerate().collect();
+        let len = list.len() as i64;
+        for _ in 0..rounds {
+            for i in 0..numbers.len() {
+                let from = list.iter().position(|(j, _)| *j == i).unwrap();
+                let item = list.remove(from);
+                let to = (from as i64 + item.1).rem_euclid(len - 1) as usize;
+                list.insert(to, item);
+            }
+        }
+
+        let zero = list.iter().position(|(_, v)| *v == 0).unwrap();
+        (1..4).map(|i| list[(zero + i * 1000) % list.len()].1).sum()
+    }
+
+    proptest! {
+        #[test]
+        fn test_mix_matches_slow(seed in any::<u64>(), size in 1..30usize) {
+            let input = generate::generate(&mut Rng::new(seed), size);
+            let numbers = aoc::parse::lines(&input, parse_number).unwrap();
+            prop_assert_eq!(grove_coordinates(&input, 1, 1).unwrap(), mix_slowly(&numbers, 1, 1));
+            prop_assert_eq!(
+                grove_coordinates(&input, 811589153, 10).unwrap(),
+                mix_slowly(&numbers, 811589153, 10)
+            );
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use aoc::Rng;
+use std::collections::HashSet;
+
+/// About `size` monkeys doing maths. Every division comes out even and no
+/// monkey yells 0, so the number for humn can be worked out backwards.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut generator = Generator {
+        rng,
+        names: HashSet::from(["root".to_string(), "humn".to_string()]),
+        lines: Vec::new(),
+        left: size.max(3),
+    };
+
+    // humn is somewhere on one side of root, the other side comes out the same
+    let value = generator.nonzero();
+    let humn_side = generator.expression(value, true);
+    let other_side = generator.expression(value, false);
+
+    let (left, right) = if generator.rng.chance(0.5) {
+        (humn_side, other_side)
+    } else {
+        (other_side, humn_side)
+    };
+    let op = *generator.rng.pick(&['+', '-', '*', '/']);
+    generator.lines.push(format!("root: {} {} {}", left, op, right));
+
+    let mut lines = generator.lines;
+    generator.rng.shuffle(&mut lines);
+    lines.join("\n") + "\n"
+}
+
+struct Generator<'a> {
+    rng: &'a mut Rng,
+    names: HashSet<String>,
+    lines: Vec<String>,
+    // How many more monkeys to make up
+    left: usize,
+}
+
+impl Generator<'_> {
+    fn name(&mut self) -> String {
+        loop {
+            let name: String = (0..4)
+                .map(|_| (b'a' + self.rng.below(26) as u8) as char)
+                .collect();
+            if self.names.insert(name.clone()) {
+                return name;
+            }
+        }
+    }
+
+    fn nonzero(&mut self) -> i64 {
+        let value = self.rng.between(1, 20);
+        if self.rng.chance(0.2) {
+            -value
+        } else {
+            value
+        }
+    }
+
+    // A monkey that yells `value`, with humn among the monkeys it listens to
+    // if `with_humn`. Returns the name of the monkey.
+    fn expression(&mut self, value: i64, with_humn: bool) -> String {
+        self.left = self.left.saturating_sub(1);
+        if self.left == 0 || value.abs() > 1_000_000_000 {
+            let name = if with_humn { "humn".to_string() } else { self.name() };
+            self.lines.push(format!("{}: {}", name, value));
+            return name;
+        }
+
+        // Operands that give `value`, neither of them 0. Only small numbers
+        // are worth looking for divisors of.
+        let ops = if value.abs() <= 10000 { 4 } else { 3 };
+        let (op, left, right) = match self.rng.below(ops) {
+            0 => {
+                let left = self.other_than(value);
+                ('+', left, value - left)
+            }
+            1 => {
+                let right = self.nonzero();
+                ('/', value * right, right)
+            }
+            2 => {
+                let right = self.other_than(-value);
+                ('-', value + right, right)
+            }
+            _ => {
+                let divisors: Vec<i64> = (1..=value.abs()).filter(|d| value % d == 0).collect();
+                let left = *self.rng.pick(&divisors);
+                ('*', left, value / left)
+            }
+        };
+
+        let humn_left = with_humn && self.rng.chance(0.5);
+        let left = self.expression(left, humn_left);
+        let right = self.expression(right, with_humn && !humn_left);
+
+        let name = self.name();
+        self.lines.push(format!("{}: {} {} {}", name, left, op, right));
+        name
+    }
+
+    // A number that isn't 0 or `value`
+    fn other_than(&mut self, value: i64) -> i64 {
+        loop {
+            let n = self.nonzero();
+            if n != value {
+                return n;
+            }
+        }
+    }
+}
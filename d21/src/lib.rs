@@ -0,0 +1,232 @@
+//! Day 21: Monkey Math. Works out the numbers the monkeys yell.
+
+use aoc::{Cursor, ParseError, Rng, Solution};
+use std::collections::HashMap;
+
+mod generate;
+
+/// What a monkey does with the numbers of the two monkeys it waits for.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub enum Operation {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+/// The numbers the monkeys yell.
+pub type ValueType = i64;
+
+/// A monkey yells a number right away, or the result of an operation on the
+/// numbers of the two monkeys named.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub enum MonkeyRequest {
+    Value(ValueType),
+    Op(Operation, String, String)
+}
+
+impl MonkeyRequest {
+    fn derive(&self, name: &str) -> Vec<(String, MonkeyRequest)> {
+        use MonkeyRequest::*;
+        use Operation::*;
+        match self {
+            Value(_) => vec![],
+            Op(Add, left, right) => {
+                vec![
+                    (right.clone(), Op(Subtract, name.to_string(), left.clone())),
+                    (left.clone(), Op(Subtract, name.to_string(), right.clone())),
+                ]
+            },
+            Op(Subtract, left, right) => {
+                vec![
+                    (left.clone(), Op(Add, name.to_string(), right.clone())),
+                    (right.clone(), Op(Subtract, left.clone(), name.to_string())),
+                ]
+            },
+            Op(Multiply, left, right) => {
+                vec![
+                    (left.clone(), Op(Divide, name.to_string(), right.clone())),
+                    (right.clone(), Op(Divide, name.to_string(), left.clone())),
+                ]
+            },
+            Op(Divide, left, right) => {
+                vec![
+                    (left.clone(), Op(Multiply, name.to_string(), right.clone())),
+                    (right.clone(), Op(Divide, left.clone(), name.to_string())),
+                ]
+            },
+        }
+    }
+}
+
+fn apply(request: &MonkeyRequest, values: &HashMap<String, ValueType>) -> Option<ValueType> {
+    match request {
+        MonkeyRequest::Value(value) => Some(*value),
+        MonkeyRequest::Op(op, left, right) => {
+            let left = values.get(left)?;
+            let right = values.get(right)?;
+            let result = match op {
+                Operation::Add => left + right,
+                Operation::Subtract => left - right,
+                Operation::Multiply => left * right,
+                Operation::Divide => {
+                    if left%right == 0 {
+                        left/right
+                    } else {
+                        return None;
+                    }
+                }
+            };
+            Some(result)
+        }
+    }
+}
+
+/// A line like `root: pppw + sjmn` or `dbpl: 5`.
+pub fn parse_monkey_line(cursor: &mut Cursor) -> Result<(String, MonkeyRequest), ParseError> {
+    let name = cursor.word()?.to_string();
+    cursor.tag(": ")?;
+
+    if cursor.peek().is_some_and(|c| c.is_ascii_digit() || c == '-') {
+        let value = cursor.number()?;
+        return Ok((name, MonkeyRequest::Value(value)));
+    }
+
+    let left = cursor.word()?.to_string();
+    cursor.tag(" ")?;
+    let op = match cursor.one_of("+-*/")? {
+        '+' => Operation::Add,
+        '-' => Operation::Subtract,
+        '*' => Operation::Multiply,
+        _ => Operation::Divide,
+    };
+    cursor.tag(" ")?;
+    let right = cursor.word()?.to_string();
+    Ok((name, MonkeyRequest::Op(op, left, right)))
+}
+
+/// Parses all monkeys, there has to be one named root.
+pub fn parse_monkeys(input: &str) -> Result<Vec<(String, MonkeyRequest)>, ParseError> {
+    let monkeys = aoc::parse::lines(input, parse_monkey_line)?;
+    if !monkeys.iter().any(|(name, _)| name == "root") {
+        return Err(ParseError::end_of_input(input, "a monkey named root"));
+    }
+    Ok(monkeys)
+}
+
+/// The number the monkey named root yells.
+pub fn root_value(input: &str) -> Result<ValueType, ParseError> {
+    let input = parse_monkeys(input)?;
+    let mut values = HashMap::new();
+
+    while !values.contains_key("root") {
+        for (name, request) in &input {
+            if values.contains_key(name) {
+                continue;
+            }
+            if let Some(value) = apply(request, &values) {
+                values.insert(name.clone(), value);
+            }
+        }
+    }
+
+    Ok(*values.get("root").unwrap())
+}
+
+/// The number to yell so that both sides of root's equation match. Every
+/// operation is also turned around, so the values can be derived from root
+/// down to humn.
+pub fn humn_value(input: &str) -> Result<ValueType, ParseError> {
+    let input = parse_monkeys(input)?;
+    let mut values = HashMap::new();
+    values.insert("root".to_string(), 0);
+
+    let input = {
+        let mut transformed: Vec<(String, MonkeyRequest)> =
+            input.iter()
+            .filter(|(name, _)| name != "humn")
+            .map(|(a,b)| (a.clone(), b.clone()))
+            .collect();
+
+        let root_pos = transformed.iter().position(|(name, _)| name == "root").unwrap();
+        let root = transformed.remove(root_pos);
+
+        let new_request = match root.1 {
+            MonkeyRequest::Value(_) => panic!("Root is a value"),
+            MonkeyRequest::Op(_op, left, right) => {
+                MonkeyRequest::Op(Operation::Subtract, left, right)
+            }
+        };
+
+        transformed.push(("root".into(), new_request));
+
+        let mut derived = vec![];
+
+        for (name, request) in &transformed {
+            derived.extend(request.derive(name));
+        }
+
+        transformed.extend(derived);
+
+        transformed
+    };
+
+    while !values.contains_key("humn") {
+        for (name, request) in &input {
+            if values.contains_key(name) {
+                continue;
+            }
+            if let Some(value) = apply(request, &values) {
+                values.insert(name.clone(), value);
+            }
+        }
+    }
+
+    Ok(*values.get("humn").unwrap())
+}
+
+/// Part 1 is the number root yells, part 2 the number humn has to yell.
+pub struct Day21;
+
+impl Solution for Day21 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        Ok(root_value(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        Ok(humn_value(input)?.to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate::generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc::Rng;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn test_humn_balances_root(seed in any::<u64>(), size in 1..40usize) {
+            let input = generate::generate(&mut Rng::new(seed), size);
+            let humn = humn_value(&input).unwrap();
+
+            // With humn yelling that number and root subtracting, root yells 0
+            let balanced: String = input
+                .lines()
+                .map(|line| {
+                    let words: Vec<&str> = line.split_whitespace().collect();
+                    match words[..] {
+                        ["humn:", _] => format!("humn: {}\n", humn),
+                        ["root:", left, _, right] => format!("root: {} - {}\n", left, right),
+                        _ => format!("{}\n", line),
+                    }
+                })
+                .collect();
+            prop_assert_eq!(root_value(&balanced).unwrap(), 0);
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use super::{Face, DIRECTIONS};
+use aoc::Rng;
+use std::collections::BTreeMap;
+
+/// A cube with sides of `size` tiles, unfolded into a random one of its
+/// nets, and a path of about `2 * size` moves.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let faces = net(rng);
+
+    let rows = faces.keys().map(|f| f.0).max().unwrap() + 1;
+    let cols = faces.keys().map(|f| f.1).max().unwrap() + 1;
+
+    let mut map: Vec<Vec<char>> = vec![vec![' '; cols * size]; rows * size];
+    for &(face_row, face_col) in faces.keys() {
+        for line in &mut map[face_row * size..(face_row + 1) * size] {
+            for tile in &mut line[face_col * size..(face_col + 1) * size] {
+                *tile = if rng.chance(0.1) { '#' } else { '.' };
+            }
+        }
+    }
+
+    // The walk starts on the first open tile of the top row
+    let first = map[0].iter().position(|c| *c != ' ').unwrap();
+    map[0][first] = '.';
+
+    let mut output: String = map
+        .iter()
+        .map(|row| row.iter().collect::<String>().trim_end().to_string() + "\n")
+        .collect();
+
+    output.push('\n');
+    output += &rng.between(1, 2 * size as i64).to_string();
+    for _ in 0..2 * size {
+        output.push(*rng.pick(&['L', 'R']));
+        output += &rng.between(1, 2 * size as i64).to_string();
+    }
+    output.push('\n');
+    output
+}
+
+// The positions of the six faces of a net of the cube, in units of faces,
+// the top row and the left column are used. Faces are added next to the ones
+// placed while they land on a side of the cube that is still free.
+fn net(rng: &mut Rng) -> BTreeMap<(usize, usize), Face> {
+    'retry: loop {
+        // Room enough for any net, wherever it starts
+        let start = (rng.below(5) + 5, rng.below(5) + 5);
+        let mut faces = BTreeMap::from([(start, Face { normal: [0, 0, -1], right: [1, 0, 0], down: [0, 1, 0] })]);
+
+        while faces.len() < 6 {
+            let candidates: Vec<((usize, usize), Face)> = faces
+                .iter()
+                .flat_map(|(&(row, col), face)| {
+                    DIRECTIONS.iter().enumerate().map(move |(dir, &(row_dir, col_dir))| {
+                        let next = ((row as i32 + row_dir) as usize, (col as i32 + col_dir) as usize);
+                        (next, face.unfolded(dir))
+                    })
+                })
+                .filter(|(next, face)| {
+                    !faces.contains_key(next) && faces.values().all(|f| f.normal != face.normal)
+                })
+                .collect();
+
+            if candidates.is_empty() {
+                continue 'retry;
+            }
+            let (next, face) = *rng.pick(&candidates);
+            faces.insert(next, face);
+        }
+
+        let top = faces.keys().map(|f| f.0).min().unwrap();
+        let left = faces.keys().map(|f| f.1).min().unwrap();
+        return faces
+            .into_iter()
+            .map(|((row, col), face)| ((row - top, col - left), face))
+            .collect();
+    }
+}
@@ -0,0 +1,352 @@
+//! Day 22: Monkey Map. Follows the path across the map, first flat and then
+//! folded into a cube.
+
+use aoc::{Cursor, Grid, ParseError, Point, Rng, Solution};
+use std::collections::HashMap;
+
+mod generate;
+
+/// A tile of the map. The force field fills the space around the map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldType {
+    Empty,
+    Wall,
+    ForceField,
+}
+
+/// The map, padded with force field to a rectangle.
+pub type Field = Grid<FieldType>;
+
+/// A step of the path.
+#[derive(Debug)]
+pub enum Move {
+    Forward(usize),
+    TurnLeft,
+    TurnRight,
+}
+
+/// The whole path.
+pub type Moves = Vec<Move>;
+
+/// The (row, column) steps of the directions, numbered the way the password
+/// counts them. Turning right goes to the next one.
+pub const DIRECTIONS: [(i32, i32); 4] = [
+    (0, 1), // right
+    (1, 0), // down
+    (0, -1), // left
+    (-1, 0), // up
+];
+
+#[allow(dead_code)]
+fn print_field(field: &Field) {
+    aoc::debug!("{}", field.render(|field_type| match field_type {
+        FieldType::Empty => '.',
+        FieldType::Wall => '#',
+        FieldType::ForceField => ' ',
+    }));
+}
+
+fn parse_moves(cursor: &mut Cursor) -> Result<Moves, ParseError> {
+    let mut moves: Moves = Vec::new();
+    while !cursor.is_empty() {
+        match cursor.peek() {
+            Some('L') => moves.push(Move::TurnLeft),
+            Some('R') => moves.push(Move::TurnRight),
+            _ => {
+                moves.push(Move::Forward(cursor.number()?));
+                continue;
+            }
+        }
+        cursor.next_char()?;
+    }
+    Ok(moves)
+}
+
+/// The map and, after an empty line, the path.
+pub fn parse_input(s: &str) -> Result<(Field, Moves), ParseError> {
+    let lines = s.lines().collect::<Vec<&str>>();
+    if lines.len() < 3 {
+        return Err(ParseError::end_of_input(s, "a map followed by a line of moves"));
+    }
+
+    let moves = {
+        let mut cursor = Cursor::new(lines.len(), lines[lines.len() - 1]);
+        let moves = parse_moves(&mut cursor)?;
+        cursor.end()?;
+        moves
+    };
+
+    let separator = Cursor::new(lines.len() - 1, lines[lines.len() - 2]);
+    separator.end()?;
+
+    let field = {
+        let field_lines = lines[..lines.len() - 2].join("\n");
+        let mut field = aoc::grid::parse_grid(&field_lines, "one of '.', '#', ' '", |c| match c {
+            '.' => Some(FieldType::Empty),
+            '#' => Some(FieldType::Wall),
+            ' ' => Some(FieldType::ForceField),
+            _ => None,
+        })?;
+        let maxl = field.iter().map(|x| x.len()).max().unwrap() + 1;
+
+        for row in field.iter_mut() {
+            while row.len() < maxl {
+                row.push(FieldType::ForceField);
+            }
+        }
+
+        Grid::from_rows(field)
+    };
+
+    Ok((field, moves))
+}
+
+fn truemod(a: i32, b: i32) -> i32 {
+    ((a % b) + b) % b
+}
+
+/// The next tile in the direction, wrapping around the edges of the map and
+/// skipping the force field.
+pub fn next_pos(pos: (usize, usize), dir: (i32, i32), field: &Field) -> (usize, usize) {
+    let (row_dir, col_dir) = dir;
+
+    let mut next = Point::new(pos.0 as i32, pos.1 as i32);
+    loop {
+        next = field.wrap(next.advanced(row_dir, col_dir));
+        if field[next] != FieldType::ForceField {
+            break;
+        }
+    }
+
+    (next.row as usize, next.col as usize)
+}
+
+/// Follows the moves from the top left open tile and returns the password.
+/// `step` gives the tile and direction after a single step forward.
+pub fn walk(
+    field: &Field,
+    moves: &Moves,
+    step: impl Fn((usize, usize), usize) -> ((usize, usize), usize),
+) -> i32 {
+    let start_row: usize = 0;
+    let start_col = field.row(0).iter().position(|x| *x == FieldType::Empty).unwrap();
+
+    let mut pos = (start_row, start_col);
+    let mut dir = 0;
+
+    for m in moves {
+        match m {
+            Move::Forward(n) => {
+                for _ in 0..*n {
+                    let (npos, ndir) = step(pos, dir);
+                    if field[npos] == FieldType::Wall {
+                        break;
+                    }
+                    pos = npos;
+                    dir = ndir;
+                }
+            },
+            Move::TurnLeft => {
+                dir = truemod(dir as i32 - 1, DIRECTIONS.len() as i32) as usize;
+            },
+            Move::TurnRight => {
+                dir = truemod(dir as i32 + 1, DIRECTIONS.len() as i32) as usize;
+            },
+        }
+    }
+
+    (pos.0 as i32 + 1)*1000 + (pos.1 as i32 + 1) * 4 + dir as i32
+}
+
+type Vec3 = [i32; 3];
+
+fn neg(a: Vec3) -> Vec3 {
+    [-a[0], -a[1], -a[2]]
+}
+
+fn add(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn scale(a: Vec3, k: i32) -> Vec3 {
+    [a[0] * k, a[1] * k, a[2] * k]
+}
+
+fn dot(a: Vec3, b: Vec3) -> i32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+// Where a face of the net ends up once the net is folded into a cube. The
+// vectors point out of the cube, along the rows to the right and along the
+// columns downwards.
+#[derive(Debug, Clone, Copy)]
+struct Face {
+    normal: Vec3,
+    right: Vec3,
+    down: Vec3,
+}
+
+impl Face {
+    // The face next to this one in the net, in the given direction
+    fn unfolded(&self, dir: usize) -> Face {
+        let Face { normal, right, down } = *self;
+        match dir {
+            0 => Face { normal: right, right: neg(normal), down },
+            1 => Face { normal: down, right, down: neg(normal) },
+            2 => Face { normal: neg(right), right: normal, down },
+            3 => Face { normal: neg(down), right, down: normal },
+            _ => panic!("Unknown direction"),
+        }
+    }
+
+    fn direction(&self, dir: usize) -> Vec3 {
+        let (row_dir, col_dir) = DIRECTIONS[dir];
+        add(scale(self.down, row_dir), scale(self.right, col_dir))
+    }
+}
+
+/// The map folded into a cube.
+pub struct Cube {
+    size: usize,
+    // keyed by the (row, col) of the face in the net, in units of faces
+    faces: HashMap<(usize, usize), Face>,
+}
+
+impl Cube {
+    /// Folds the net on the map. Panics if it isn't the net of a cube.
+    pub fn fold(field: &Field) -> Cube {
+        let area = field.iter().filter(|(_, x)| **x != FieldType::ForceField).count();
+        let size = ((area / 6) as f64).sqrt().round() as usize;
+
+        let is_face = |face: (usize, usize)| {
+            let (row, col) = (face.0 * size, face.1 * size);
+            row < field.height() && col < field.width() && field[(row, col)] != FieldType::ForceField
+        };
+
+        let first = (0, field.row(0).iter().position(|x| *x != FieldType::ForceField).unwrap() / size);
+        let mut faces = HashMap::new();
+        faces.insert(first, Face { normal: [0, 0, -1], right: [1, 0, 0], down: [0, 1, 0] });
+        let mut queue = vec![first];
+
+        while let Some(pos) = queue.pop() {
+            let face = faces[&pos];
+            for (dir, (row_dir, col_dir)) in DIRECTIONS.iter().enumerate() {
+                let row = pos.0 as i32 + row_dir;
+                let col = pos.1 as i32 + col_dir;
+                if row < 0 || col < 0 {
+                    continue;
+                }
+                let next = (row as usize, col as usize);
+                if is_face(next) && !faces.contains_key(&next) {
+                    faces.insert(next, face.unfolded(dir));
+                    queue.push(next);
+                }
+            }
+        }
+
+        assert_eq!(faces.len(), 6, "The map does not fold into a cube");
+
+        Cube { size, faces }
+    }
+
+    // Cell centres are in doubled coordinates around the middle of the cube,
+    // so that they stay whole numbers.
+    fn to_space(&self, face: &Face, row: usize, col: usize) -> Vec3 {
+        let n = self.size as i32;
+        add(
+            scale(face.normal, n),
+            add(
+                scale(face.right, 2 * col as i32 + 1 - n),
+                scale(face.down, 2 * row as i32 + 1 - n),
+            ),
+        )
+    }
+
+    /// Length of an edge of the cube, in tiles.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The tile and direction after a step forward, going over the edges of
+    /// the cube to the faces next to it.
+    pub fn step(&self, pos: (usize, usize), dir: usize) -> ((usize, usize), usize) {
+        let size = self.size;
+        let face_pos = (pos.0 / size, pos.1 / size);
+        let (row, col) = (pos.0 % size, pos.1 % size);
+        let (row_dir, col_dir) = DIRECTIONS[dir];
+
+        let next_row = row as i32 + row_dir;
+        let next_col = col as i32 + col_dir;
+        if (0..size as i32).contains(&next_row) && (0..size as i32).contains(&next_col) {
+            let next = ((pos.0 as i32 + row_dir) as usize, (pos.1 as i32 + col_dir) as usize);
+            return (next, dir);
+        }
+
+        // Over the edge: we end up on the face that points the way we were
+        // going, heading away from the face we left.
+        let face = self.faces[&face_pos];
+        let heading = face.direction(dir);
+        let (&next_face_pos, next_face) = self.faces
+            .iter()
+            .find(|(_, f)| f.normal == heading)
+            .unwrap();
+
+        let space = add(self.to_space(&face, row, col), add(heading, neg(face.normal)));
+        let n = size as i32;
+        let next_row = ((dot(space, next_face.down) + n - 1) / 2) as usize;
+        let next_col = ((dot(space, next_face.right) + n - 1) / 2) as usize;
+        let next_dir = (0..DIRECTIONS.len())
+            .find(|d| next_face.direction(*d) == neg(face.normal))
+            .unwrap();
+
+        (
+            (next_face_pos.0 * size + next_row, next_face_pos.1 * size + next_col),
+            next_dir,
+        )
+    }
+}
+
+/// Part 1 wraps around the edges of the flat map, part 2 walks over the
+/// edges of the cube.
+pub struct Day22;
+
+impl Solution for Day22 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        let (field, moves) = parse_input(input)?;
+        Ok(walk(&field, &moves, |pos, dir| (next_pos(pos, DIRECTIONS[dir], &field), dir)).to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        let (field, moves) = parse_input(input)?;
+        let cube = Cube::fold(&field);
+        Ok(walk(&field, &moves, |pos, dir| cube.step(pos, dir)).to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate::generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample() {
+        let sample = include_str!("../sample.txt");
+        assert_eq!(Day22.part1(sample).unwrap(), "6032");
+        assert_eq!(Day22.part2(sample).unwrap(), "5031");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_input("..#\n.x.\n\n10R5").unwrap_err(),
+            ParseError::new(2, 2, "one of '.', '#', ' '", "\"x.\"")
+        );
+        assert_eq!(
+            parse_input("..#\n...\n\n10R5U").unwrap_err(),
+            ParseError::new(4, 5, "a number", "\"U\"")
+        );
+    }
+}
@@ -0,0 +1,14 @@
+use aoc::Rng;
+
+/// A `size` by `size` scan with about half of it elves.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    (0..size)
+        .map(|_| {
+            (0..size)
+                .map(|_| if rng.chance(0.5) { '#' } else { '.' })
+                .collect::<String>()
+                + "\n"
+        })
+        .collect()
+}
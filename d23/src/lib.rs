@@ -0,0 +1,225 @@
+//! Day 23: Unstable Diffusion. The elves spread out over the grove.
+
+use aoc::Point as Elf;
+use aoc::visual::Frame;
+use aoc::{Grid, ParseError, Recorder, Rng, Solution};
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+mod generate;
+
+fn advanced_in_direction(elf: &Elf, direction: &Direction) -> (Elf, (Elf, Elf)) {
+    match direction {
+        Direction::Up => (
+            elf.advanced(-1, 0),
+            (elf.advanced(-1, 1), elf.advanced(-1, -1)),
+        ),
+        Direction::Down => (
+            elf.advanced(1, 0),
+            (elf.advanced(1, 1), elf.advanced(1, -1)),
+        ),
+        Direction::Left => (
+            elf.advanced(0, -1),
+            (elf.advanced(-1, -1), elf.advanced(1, -1)),
+        ),
+        Direction::Right => (
+            elf.advanced(0, 1),
+            (elf.advanced(-1, 1), elf.advanced(1, 1)),
+        ),
+    }
+}
+
+/// The positions of all elves.
+pub type ElfSet = HashSet<Elf>;
+
+/// The elves are the '#' of the scan.
+pub fn parse_elves(input: &str) -> Result<ElfSet, ParseError> {
+    let grid = Grid::parse(input, "one of '#', '.'", |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })?;
+
+    Ok(grid.iter().filter(|(_, &elf)| elf).map(|(elf, _)| elf).collect())
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+fn get_moves(elf: &Elf, positions: &ElfSet, directions: &[Direction]) -> Elf {
+    let mut found = false;
+    for dx in -1..=1 {
+        for dy in -1..=1 {
+            if dx == 0 && dy == 0 {
+                continue;
+            }
+
+            let new_elf = elf.advanced(dx, dy);
+            if positions.contains(&new_elf) {
+                found = true;
+                break;
+            }
+        }
+    }
+
+    if !found {
+        return *elf;
+    }
+
+    for direction in directions {
+        let (new_elf, (left, right)) = advanced_in_direction(elf, direction);
+        if positions.contains(&new_elf) {
+            continue;
+        }
+
+        if positions.contains(&left) || positions.contains(&right) {
+            continue;
+        }
+
+        return new_elf;
+    }
+
+    *elf
+}
+
+// The smallest rectangle that holds all elves
+fn elves_frame(elves: &ElfSet) -> Frame {
+    let min_row = elves.iter().map(|elf| elf.row).min().unwrap();
+    let max_row = elves.iter().map(|elf| elf.row).max().unwrap();
+
+    let min_col = elves.iter().map(|elf| elf.col).min().unwrap();
+    let max_col = elves.iter().map(|elf| elf.col).max().unwrap();
+
+    let height = (max_row - min_row + 1) as usize;
+    let width = (max_col - min_col + 1) as usize;
+    let mut frame = Grid::new(height, width, '.');
+    for elf in elves {
+        frame[elf.advanced(-min_row, -min_col)] = '#';
+    }
+    frame
+}
+
+/// Moves every elf once; the order of the directions they consider rotates
+/// with each round, starting from round 0.
+pub fn play_round(elves: &ElfSet, round: usize) -> ElfSet {
+    let directions = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    let mut directions_iter = directions.iter().cycle().skip(round);
+    let directions = [
+        *directions_iter.next().unwrap(),
+        *directions_iter.next().unwrap(),
+        *directions_iter.next().unwrap(),
+        *directions_iter.next().unwrap(),
+    ];
+
+    let proposed_positions: Vec<_> = elves
+        .iter()
+        .map(|elf| (*elf, get_moves(elf, elves, &directions)))
+        .collect();
+
+    // get hashmap with counts of new_positions
+    let posisition_count = proposed_positions
+        .iter()
+        .map(|(_, new_position)| new_position)
+        .fold(HashMap::new(), |mut acc, new_position| {
+            *acc.entry(new_position).or_insert(0) += 1;
+            acc
+        });
+
+    let mut new_elves = ElfSet::new();
+
+    for (elf, new_position) in &proposed_positions {
+        if posisition_count[new_position] == 1 {
+            new_elves.insert(*new_position);
+        } else {
+            new_elves.insert(*elf);
+        }
+    }
+
+    new_elves
+}
+
+/// Empty tiles in the smallest rectangle containing every elf.
+pub fn empty_ground(elves: &ElfSet) -> i32 {
+    let min_row = elves.iter().map(|elf| elf.row).min().unwrap();
+    let max_row = elves.iter().map(|elf| elf.row).max().unwrap();
+
+    let min_col = elves.iter().map(|elf| elf.col).min().unwrap();
+    let max_col = elves.iter().map(|elf| elf.col).max().unwrap();
+
+    let drow = max_row - min_row + 1;
+    let dcol = max_col - min_col + 1;
+
+    drow*dcol - elves.len() as i32
+}
+
+/// Plays rounds until no elf moves anymore and returns the number of that
+/// round. Every round is a frame for `recorder`.
+pub fn settle(input: &str, recorder: &mut Recorder) -> Result<usize, ParseError> {
+    let mut elves = parse_elves(input)?;
+    recorder.capture(|| elves_frame(&elves));
+
+    let mut round = 0;
+    loop {
+        let new_elves = play_round(&elves, round);
+        if new_elves == elves {
+            break;
+        }
+
+        elves = new_elves;
+        recorder.capture(|| elves_frame(&elves));
+        round += 1;
+    }
+
+    Ok(round + 1)
+}
+
+/// Part 1 counts the empty ground after 10 rounds, part 2 is the first round
+/// in which no elf moves.
+pub struct Day23;
+
+impl Solution for Day23 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        let mut elves = parse_elves(input)?;
+        for round in 0..10 {
+            elves = play_round(&elves, round);
+        }
+        Ok(empty_ground(&elves).to_string())
+    }
+
+    // The first round in which no elf moves
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        Ok(settle(input, &mut Recorder::off())?.to_string())
+    }
+
+    fn record(&self, input: &str, recorder: &mut Recorder) -> Result<(), ParseError> {
+        settle(input, recorder)?;
+        Ok(())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate::generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample() {
+        let sample = include_str!("../sample.txt");
+        assert_eq!(Day23.part1(sample).unwrap(), "110");
+        assert_eq!(Day23.part2(sample).unwrap(), "20");
+    }
+}
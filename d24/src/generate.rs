@@ -0,0 +1,33 @@
+use super::{parse_field, search_trip};
+use aoc::Rng;
+
+/// A valley `size` rows high and one and a half times as wide, a third of it
+/// blizzards. Valleys the expedition can't get through and back are thrown
+/// away and made up again.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let rows = size.max(1);
+    let cols = (3 * rows / 2).max(2);
+
+    loop {
+        let mut valley = format!("#.{}\n", "#".repeat(cols));
+        for _ in 0..rows {
+            valley.push('#');
+            for col in 0..cols {
+                // Nothing blows out through the entrance or the exit
+                let directions: &[char] = if col == 0 || col == cols - 1 {
+                    &['<', '>']
+                } else {
+                    &['<', '>', '^', 'v']
+                };
+                valley.push(if rng.chance(0.3) { *rng.pick(directions) } else { '.' });
+            }
+            valley += "#\n";
+        }
+        valley += &format!("{}.#\n", "#".repeat(cols));
+
+        let field = parse_field(&valley).unwrap();
+        if search_trip(&field, 2).1.goal().is_some() {
+            return valley;
+        }
+    }
+}
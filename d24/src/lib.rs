@@ -0,0 +1,365 @@
+//! Day 24: Blizzard Basin. Finds the quickest way through the valley while
+//! the blizzards move.
+
+use aoc::visual::Frame;
+use aoc::search::{self, Search};
+use aoc::{Grid, ParseError, Point, Recorder, Rng, Solution};
+use std::collections::HashSet;
+use std::fmt;
+
+mod generate;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Blizzard {
+    direction: Direction,
+    position: Point,
+}
+
+impl Blizzard {
+    fn advanced_once(&self, valley: &Grid<bool>) -> Blizzard {
+        let (drow, dcol) = match self.direction {
+            Direction::Up => (-1, 0),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+            Direction::Right => (0, 1),
+        };
+
+        Blizzard {
+            direction: self.direction,
+            position: valley.wrap(self.position.advanced(drow, dcol)),
+        }
+    }
+}
+
+/// The valley inside the walls, the start and end are just outside of it.
+/// It prints the way it is written in the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field {
+    blizzards: HashSet<Blizzard>,
+    occupied: Grid<bool>,
+    modulo: i32,
+}
+
+impl Field {
+    fn new(rows: usize, cols: usize, blizzards: HashSet<Blizzard>) -> Field {
+        let mut occupied = Grid::new(rows, cols, false);
+        for blizzard in &blizzards {
+            occupied[blizzard.position] = true;
+        }
+
+        Field {
+            blizzards,
+            occupied,
+            modulo: lcm(rows as i32, cols as i32),
+        }
+    }
+
+    /// The valley a minute later, after every blizzard moved a step.
+    pub fn advanced(&self) -> Field {
+        let new_blizzards_iter = self
+            .blizzards
+            .iter()
+            .map(|blizzard| blizzard.advanced_once(&self.occupied));
+        Field::new(self.occupied.height(), self.occupied.width(), new_blizzards_iter.collect())
+    }
+
+    fn rows(&self) -> i32 {
+        self.occupied.height() as i32
+    }
+
+    fn cols(&self) -> i32 {
+        self.occupied.width() as i32
+    }
+
+    fn is_valid_position(&self, state: &State) -> bool {
+        let row = state.row;
+        let col = state.col;
+
+        match self.occupied.get(Point::new(row, col)) {
+            Some(&occupied) => !occupied,
+            // Only start and end outside of fields are acceptable
+            None => {
+                let possible_values = [(-1, 0), (self.rows(), self.cols() - 1)];
+                possible_values.contains(&(row, col))
+            }
+        }
+    }
+
+    fn start_row() -> i32 {
+        -1
+    }
+
+    fn start_col() -> i32 {
+        0
+    }
+
+    fn end_row(&self) -> i32 {
+        self.rows()
+    }
+
+    fn end_col(&self) -> i32 {
+        self.cols() - 1
+    }
+
+    fn is_start(&self, state: &State) -> bool {
+        state.row == Field::start_row() && state.col == Field::start_col()
+    }
+
+    fn is_end(&self, state: &State) -> bool {
+        state.row == self.end_row() && state.col == self.end_col()
+    }
+}
+
+impl Field {
+    // The valley with its walls, and the expedition as 'E' if it's given
+    fn frame(&self, expedition: Option<&State>) -> Frame {
+        let rows = self.occupied.height() + 2;
+        let cols = self.occupied.width() + 2;
+        let mut field = Grid::new(rows, cols, '#');
+
+        for point in self.occupied.points() {
+            field[point.advanced(1, 1)] = '.';
+        }
+
+        for blizzard in &self.blizzards {
+            let symbol = match blizzard.direction {
+                Direction::Up => '^',
+                Direction::Down => 'v',
+                Direction::Left => '<',
+                Direction::Right => '>',
+            };
+            let cur_symbol = &mut field[blizzard.position.advanced(1, 1)];
+            let next_symbol = match cur_symbol {
+                '.' => symbol,
+                _ => 'X',
+            };
+
+            *cur_symbol = next_symbol;
+        }
+
+        field[(0, 1)] = '.';
+        field[(rows - 1, cols - 2)] = '.';
+
+        if let Some(state) = expedition {
+            field[Point::new(state.row + 1, state.col + 1)] = 'E';
+        }
+
+        field
+    }
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.frame(None).render(|c| *c))
+    }
+}
+
+/// The valley with its walls and blizzards.
+pub fn parse_field(s: &str) -> Result<Field, ParseError> {
+    let grid = Grid::parse(s, "one of '#', '.', '^', 'v', '<', '>'", |c| match c {
+        '^' => Some(Some(Direction::Up)),
+        'v' => Some(Some(Direction::Down)),
+        '<' => Some(Some(Direction::Left)),
+        '>' => Some(Some(Direction::Right)),
+        '#' | '.' => Some(None),
+        _ => None,
+    })?;
+
+    // The valley needs at least one open row and column inside its walls
+    if grid.height() < 3 {
+        return Err(ParseError::end_of_input(s, "a row"));
+    }
+    if grid.width() < 3 {
+        return Err(ParseError::new(1, grid.width() + 1, "a wider valley", "end of line"));
+    }
+
+    let mut blizzards = HashSet::new();
+    for (point, direction) in grid.iter() {
+        if let Some(direction) = *direction {
+            blizzards.insert(Blizzard {
+                direction,
+                position: point.advanced(-1, -1),
+            });
+        }
+    }
+
+    Ok(Field::new(grid.height() - 2, grid.width() - 2, blizzards))
+}
+
+fn gcd(a: i32, b: i32) -> i32 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+fn lcm(a: i32, b: i32) -> i32 {
+    a * b / gcd(a, b)
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct State {
+    row: i32,
+    col: i32,
+    offset: i32,
+    phase: i32,
+}
+
+impl State {
+    fn advanced_in_direction(&self, field: &Field, direction: Direction) -> Option<State> {
+        let (row, col) = match direction {
+            Direction::Up => (self.row - 1, self.col),
+            Direction::Down => (self.row + 1, self.col),
+            Direction::Left => (self.row, self.col - 1),
+            Direction::Right => (self.row, self.col + 1),
+        };
+
+        let mut next_state = State {
+            row,
+            col,
+            offset: (self.offset + 1) % field.modulo,
+            phase: self.phase,
+        };
+
+        if self.phase == 0 && field.is_end(&next_state) {
+            next_state.phase = 1;
+        }
+
+        if self.phase == 1 && field.is_start(&next_state) {
+            next_state.phase = 2;
+        }
+
+        if field.is_valid_position(&next_state) {
+            Some(next_state)
+        } else {
+            None
+        }
+    }
+
+    fn possible_next(&self, next_field: &Field) -> Vec<State> {
+        let mut possible_next = vec![];
+
+        for direction in &[
+            Direction::Up,
+            Direction::Down,
+            Direction::Left,
+            Direction::Right,
+        ] {
+            if let Some(next_state) = self.advanced_in_direction(next_field, *direction) {
+                possible_next.push(next_state);
+            }
+        }
+
+        // stay
+        let stay_state = State {
+            row: self.row,
+            col: self.col,
+            phase: self.phase,
+            offset: (self.offset + 1) % next_field.modulo,
+        };
+
+        if next_field.is_valid_position(&stay_state) {
+            possible_next.push(stay_state);
+        }
+
+        possible_next
+    }
+}
+
+// Every state of the valley until it repeats, and the search for the
+// quickest way to the end in the given phase: phase 1 goes straight there,
+// phase 2 has also been back to the start in between.
+fn search_trip(initial_field: &Field, phase: i32) -> (Vec<Field>, Search<State, usize>) {
+    let modulo = initial_field.modulo;
+
+    let fields = {
+        let mut fields = vec![initial_field.clone()];
+
+        for _ in 0..modulo {
+            let field = fields.last().unwrap().advanced();
+            fields.push(field);
+        }
+
+        fields
+    };
+
+    let initial_state = State {
+        row: -1,
+        col: 0,
+        offset: 0,
+        phase: 0,
+    };
+
+    let search = search::bfs(
+        [initial_state],
+        |state| {
+            let next_modulo = (state.offset + 1) % modulo;
+            state.possible_next(&fields[next_modulo as usize])
+        },
+        |state| state.phase == phase && initial_field.is_end(state),
+    );
+
+    (fields, search)
+}
+
+/// Shortest time to be at the end in the given phase: phase 1 goes straight
+/// there, phase 2 goes back for the snacks first. Fails if the blizzards
+/// never let anyone through. Every minute of the way is a frame for
+/// `recorder`.
+pub fn fastest_trip(input: &str, phase: i32, recorder: &mut Recorder) -> Result<i32, ParseError> {
+    let (fields, search) = search_trip(&parse_field(input)?, phase);
+
+    if recorder.is_recording() {
+        for state in search.goal_path().into_iter().flatten() {
+            recorder.capture(|| fields[state.offset as usize].frame(Some(&state)));
+        }
+    }
+
+    let minutes = search
+        .goal_cost()
+        .ok_or_else(|| ParseError::new(1, 1, "a valley the blizzards let anyone through", "no way through"))?;
+    Ok(minutes as i32)
+}
+
+/// Part 1 crosses the valley once, part 2 crosses it, goes back and crosses
+/// it again.
+pub struct Day24;
+
+impl Solution for Day24 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        Ok(fastest_trip(input, 1, &mut Recorder::off())?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        Ok(fastest_trip(input, 2, &mut Recorder::off())?.to_string())
+    }
+
+    fn record(&self, input: &str, recorder: &mut Recorder) -> Result<(), ParseError> {
+        fastest_trip(input, 2, recorder)?;
+        Ok(())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate::generate(rng, size)
+    }
+}
+
+#[test]
+fn test_no_way_through() {
+    // The only cell of the valley always has a blizzard in it
+    let blocked = "#.#\n#v#\n#.#\n";
+    let no_way = ParseError::new(1, 1, "a valley the blizzards let anyone through", "no way through");
+    assert_eq!(fastest_trip(blocked, 1, &mut Recorder::new()), Err(no_way.clone()));
+    assert_eq!(fastest_trip(blocked, 2, &mut Recorder::off()), Err(no_way));
+    assert_eq!(fastest_trip(include_str!("../sample.txt"), 1, &mut Recorder::off()), Ok(18));
+}
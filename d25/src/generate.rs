@@ -0,0 +1,12 @@
+use super::SNAFU;
+use aoc::Rng;
+
+/// `size` fuel requirements in SNAFU, of up to 13 digits each.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            let digits = rng.between(1, 13) as u32;
+            format!("{}\n", SNAFU::from(rng.between(1, 5i64.pow(digits) / 2)))
+        })
+        .collect()
+}
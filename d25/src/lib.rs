@@ -0,0 +1,226 @@
+//! Day 25: Full of Hot Air. Adds up the fuel requirements in SNAFU, balanced
+//! base five.
+
+use aoc::{Cursor, ParseError, Rng, Solution};
+
+mod generate;
+
+/// A number in balanced base five, with the digits `=`, `-`, `0`, `1` and `2`
+/// for -2 to 2. It converts to and from integers and prints the way it is
+/// written.
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SNAFU {
+    values: Vec<i32>,
+}
+
+impl From<i64> for SNAFU {
+    fn from(input: i64) -> Self {
+        fn step_once(val: i64) -> (i64, i32) {
+            let (div, rem) = (val / 5, val % 5);
+            if rem > 2 {
+                (div + 1, (rem - 5) as i32)
+            } else {
+                (div, rem as i32)
+            }
+        }
+        let mut values = Vec::new();
+
+        let mut input = input;
+
+        while input > 0 {
+            let (new_input, last) = step_once(input);
+            values.push(last);
+            input = new_input;
+        }
+
+        SNAFU { values }
+    }
+}
+
+impl From<i32> for SNAFU {
+    fn from(input: i32) -> Self {
+        SNAFU::from(input as i64)
+    }
+}
+
+impl From<&SNAFU> for i64 {
+    fn from(v: &SNAFU) -> Self {
+        let mut result: i64 = 0;
+        let mut multiplier: i64 = 1;
+        for value in v.values.iter() {
+            result += (*value as i64) * multiplier;
+            multiplier *= 5;
+        }
+        result
+    }
+}
+
+impl From<SNAFU> for i64 {
+    fn from(v: SNAFU) -> Self {
+        i64::from(&v)
+    }
+}
+
+impl SNAFU {
+    /// The digits of a number, most significant first.
+    pub fn parse(cursor: &mut Cursor) -> Result<SNAFU, ParseError> {
+        let mut values = Vec::new();
+        loop {
+            let value = match cursor.one_of("=-012")? {
+                '=' => -2,
+                '-' => -1,
+                '0' => 0,
+                '1' => 1,
+                _ => 2,
+            };
+            values.push(value);
+
+            if cursor.is_empty() {
+                break;
+            }
+        }
+
+        values.reverse();
+
+        Ok(SNAFU { values })
+    }
+}
+
+impl std::str::FromStr for SNAFU {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cursor = Cursor::new(1, s);
+        let snafu = SNAFU::parse(&mut cursor)?;
+        cursor.end()?;
+        Ok(snafu)
+    }
+}
+
+// implements formatter for SNAFU
+impl std::fmt::Display for SNAFU {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for value in self.values.iter().rev() {
+            let c: char = match value {
+                -2 => '=',
+                -1 => '-',
+                0 => '0',
+                1 => '1',
+                2 => '2',
+                _ => unreachable!(),
+            };
+            write!(f, "{}", c)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_transform_samples() -> Vec<(&'static str, &'static str)> {
+        include_str!("../sample_transforms.txt")
+            .lines()
+            .map(|line| {
+                let mut parts = line.split_whitespace();
+                let snafu_str = parts.next().unwrap();
+                let input_str = parts.next().unwrap();
+                (snafu_str, input_str)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_and_transform() {
+        for (snafu_str, input_str) in get_transform_samples() {
+            let snafu = snafu_str.parse::<SNAFU>().unwrap();
+            let input_i = input_str.parse::<i32>().unwrap();
+
+            let snafu_to_i = i64::from(snafu.clone()) as i32;
+            assert_eq!(
+                snafu_to_i, input_i,
+                "SNAFU {} should be {}, got {}",
+                snafu_str, input_i, snafu_to_i
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_and_print() {
+        for (snafu_str, _) in get_transform_samples() {
+            let snafu = snafu_str.parse::<SNAFU>().unwrap();
+            let snafu_str2 = snafu.to_string();
+            assert_eq!(snafu_str, snafu_str2, "SNAFU {} fot parsed to {}", snafu_str, snafu_str2);
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            "1=3".parse::<SNAFU>(),
+            Err(ParseError::new(1, 3, "one of '=', '-', '0', '1', '2'", "\"3\""))
+        );
+        assert_eq!(
+            "".parse::<SNAFU>(),
+            Err(ParseError::new(1, 1, "one of '=', '-', '0', '1', '2'", "end of line"))
+        );
+    }
+
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn test_roundtrip_from_i(input in 0i32..) {
+            let snafu = SNAFU::from(input);
+            let back_to_i = i64::from(snafu) as i32;
+            assert_eq!(back_to_i, input);
+        }
+    }
+}
+
+/// A number of the input, one per line.
+pub fn parse_snafu(cursor: &mut Cursor) -> Result<SNAFU, ParseError> {
+    SNAFU::parse(cursor)
+}
+
+/// Sum of the numbers.
+pub fn solve_part1(input: &[SNAFU]) -> i64 {
+    let mut result = 0;
+    for snafu in input {
+        let i = i64::from(snafu);
+        result += i;
+    }
+    result
+}
+
+#[test]
+fn test_sample() {
+    let input = aoc::parse::lines(include_str!("../sample.txt"), parse_snafu).unwrap();
+    let result = solve_part1(&input);
+    assert_eq!(result, 4890);
+}
+
+/// The only part is the sum of the numbers, written in SNAFU.
+pub struct Day25;
+
+impl Solution for Day25 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        let input = aoc::parse::lines(input, parse_snafu)?;
+        Ok(SNAFU::from(solve_part1(&input)).to_string())
+    }
+
+    // Day 25 only has one puzzle
+    fn part2(&self, _input: &str) -> Result<String, ParseError> {
+        Ok(String::from("-"))
+    }
+
+    fn parts(&self) -> u8 {
+        1
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate::generate(rng, size)
+    }
+}
@@ -0,0 +1,49 @@
+use super::ITEMS;
+use aoc::Rng;
+
+/// `size` groups of three rucksacks. Every rucksack has exactly one item in
+/// both compartments and every group exactly one item all three carry.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let items: Vec<char> = ITEMS.chars().collect();
+    let mut output = String::new();
+
+    for _ in 0..size.max(1) {
+        let mut others = items.clone();
+        rng.shuffle(&mut others);
+        let badge = others.pop().unwrap();
+
+        // The elves of a group have nothing in common but the badge
+        let share = others.len() / 3;
+        for elf in others.chunks(share).take(3) {
+            output += &rucksack(rng, elf, badge);
+            output.push('\n');
+        }
+    }
+
+    output
+}
+
+// A rucksack with the badge and items from `own`, one of which ends up in
+// both compartments
+fn rucksack(rng: &mut Rng, own: &[char], badge: char) -> String {
+    let mut own = own.to_vec();
+    own.push(badge);
+    rng.shuffle(&mut own);
+
+    let misplaced = own[0];
+    let split = rng.between(1, own.len() as i64 - 1) as usize;
+    let (mut first, mut second) = (own[..split].to_vec(), own[split..].to_vec());
+    second.push(misplaced);
+
+    // Both compartments hold as many items, repeating their own ones
+    let length = first.len().max(second.len()) + rng.below(4);
+    for compartment in [&mut first, &mut second] {
+        while compartment.len() < length {
+            let item = *rng.pick(compartment);
+            compartment.push(item);
+        }
+        rng.shuffle(compartment);
+    }
+
+    first.iter().chain(second.iter()).collect()
+}
@@ -0,0 +1,330 @@
+//! Day 3: Rucksack Reorganization. Finds the items that rucksacks, or groups
+//! of three of them, have in common, with the items of each kept as a bit mask.
+
+use aoc::{Cursor, ParseError, Rng, Solution};
+use std::fmt;
+use std::ops::{BitAnd, BitOr};
+
+mod generate;
+
+const ITEMS: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// A set of items, as a mask with the bit of each item's priority set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Items(u64);
+
+impl Items {
+    /// Every item there is.
+    pub const ALL: Items = Items(((1 << 52) - 1) << 1);
+
+    /// Just `item`, which has to be one of `a` to `z` or `A` to `Z`.
+    pub fn item(item: char) -> Items {
+        let index = ITEMS.find(item).unwrap_or_else(|| panic!("{:?} is not an item", item));
+        Items(1 << (index + 1))
+    }
+
+    /// The items of a rucksack or a compartment.
+    pub fn of(items: &str) -> Items {
+        items.chars().fold(Items::default(), |set, item| set | Items::item(item))
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn contains(self, item: char) -> bool {
+        !(self & Items::item(item)).is_empty()
+    }
+
+    /// The items in order of priority.
+    pub fn iter(self) -> impl Iterator<Item = char> {
+        ITEMS.chars().enumerate().filter(move |(i, _)| self.0 & 1 << (i + 1) != 0).map(|(_, item)| item)
+    }
+
+    /// The item of lowest priority.
+    pub fn first(self) -> Option<char> {
+        self.iter().next()
+    }
+
+    /// The sum of the priorities of the items.
+    pub fn priority(self) -> u32 {
+        (1..=52).filter(|bit| self.0 & 1 << bit != 0).sum()
+    }
+}
+
+impl BitAnd for Items {
+    type Output = Items;
+
+    fn bitand(self, other: Items) -> Items {
+        Items(self.0 & other.0)
+    }
+}
+
+impl BitOr for Items {
+    type Output = Items;
+
+    fn bitor(self, other: Items) -> Items {
+        Items(self.0 | other.0)
+    }
+}
+
+/// A rucksack is a line of items, split evenly between two compartments.
+pub fn parse_rucksack<'a>(cursor: &mut Cursor<'a>) -> Result<&'a str, ParseError> {
+    let line = cursor.rest();
+    let mut count = 0;
+    while !cursor.is_empty() {
+        cursor.one_of(ITEMS)?;
+        count += 1;
+    }
+
+    if count == 0 || count % 2 == 1 {
+        return Err(cursor.error("an even number of items"));
+    }
+    Ok(line)
+}
+
+/// The items of the two compartments of a rucksack.
+pub fn compartments(line: &str) -> (Items, Items) {
+    let (first, second) = line.split_at(line.len() / 2);
+    (Items::of(first), Items::of(second))
+}
+
+/// The items every one of `rucksacks` holds.
+pub fn common<'a>(rucksacks: impl IntoIterator<Item = &'a str>) -> Items {
+    rucksacks.into_iter().fold(Items::ALL, |items, rucksack| items & Items::of(rucksack))
+}
+
+/// Item that is in both compartments of a rucksack.
+pub fn get_misplaced_item(line: &str) -> char {
+    let (first, second) = compartments(line);
+    (first & second).first().expect("an item in both compartments")
+}
+
+#[test]
+fn test_get_misplaced_item() {
+    assert_eq!(get_misplaced_item("vJrwpWtwJgWrhcsFMMfFFhFp"), 'p');
+    assert_eq!(get_misplaced_item("abcb"), 'b');
+}
+
+/// Item that is in all three rucksacks of a group, its badge.
+pub fn get_duplicate_item(line1: &str, line2: &str, line3: &str) -> char {
+    common([line1, line2, line3]).first().expect("an item in all three rucksacks")
+}
+
+#[test]
+fn test_get_duplicate_item() {
+    assert_eq!(get_duplicate_item("ab", "ac", "ae"), 'a');
+    assert_eq!(get_duplicate_item("xav", "bqx", "plx"), 'x');
+}
+
+/// Priority of an item: 1 to 26 for `a` to `z`, 27 to 52 for `A` to `Z`.
+pub fn get_score(c: char) -> i32 {
+    Items::item(c).priority() as i32
+}
+
+// The one item in `items`, or an error for line `line` that lists what there
+// is instead
+fn the_one(items: Items, line: usize, expected: &str) -> Result<char, ParseError> {
+    match items.len() {
+        1 => Ok(items.first().unwrap()),
+        0 => Err(ParseError::new(line, 1, expected, "none")),
+        _ => {
+            let found: Vec<String> = items.iter().map(|item| item.to_string()).collect();
+            Err(ParseError::new(line, 1, expected, found.join(", ")))
+        }
+    }
+}
+
+/// A group of rucksacks, from line `line` on, and the items more than one of
+/// them holds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Group {
+    pub line: usize,
+    pub size: usize,
+    /// The items all the rucksacks hold
+    pub common: Items,
+    /// Every item in two or more of the rucksacks, with how many hold it, in
+    /// order of priority
+    pub shared: Vec<(char, usize)>,
+}
+
+impl Group {
+    pub fn new(line: usize, rucksacks: &[&str]) -> Group {
+        let sets: Vec<Items> = rucksacks.iter().map(|rucksack| Items::of(rucksack)).collect();
+        let shared = ITEMS
+            .chars()
+            .map(|item| (item, sets.iter().filter(|set| set.contains(item)).count()))
+            .filter(|&(_, count)| count > 1)
+            .collect();
+        Group {
+            line,
+            size: rucksacks.len(),
+            common: common(rucksacks.iter().copied()),
+            shared,
+        }
+    }
+
+    /// The item all rucksacks of the group hold. It's an error for there to
+    /// be none or several.
+    pub fn badge(&self) -> Result<char, ParseError> {
+        the_one(self.common, self.line, "one item every rucksack of the group holds")
+    }
+}
+
+impl fmt::Display for Group {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let shared: Vec<String> = self.shared.iter().map(|(item, count)| format!("{} in {}", item, count)).collect();
+        write!(f, "{:5}  {}", self.line, shared.join(", "))
+    }
+}
+
+/// Splits the rucksacks into groups of `size`. The last group can't be short.
+pub fn groups(input: &str, size: usize) -> Result<Vec<Group>, ParseError> {
+    assert!(size > 0, "Groups need at least one rucksack");
+    let lines = aoc::parse::lines(input, parse_rucksack)?;
+    let missing = (size - lines.len() % size) % size;
+    if missing > 0 {
+        return Err(ParseError::end_of_input(
+            input,
+            format!("{} more rucksacks for the last group of {}", missing, size),
+        ));
+    }
+
+    Ok(lines
+        .chunks(size)
+        .enumerate()
+        .map(|(i, group)| Group::new(i * size + 1, group))
+        .collect())
+}
+
+/// The sum of the priorities of the badges of groups of `size`.
+pub fn badge_sum(input: &str, size: usize) -> Result<i32, ParseError> {
+    groups(input, size)?
+        .iter()
+        .map(|group| Ok(get_score(group.badge()?)))
+        .sum()
+}
+
+/// Part 1 sums the priorities of the misplaced items, part 2 those of the
+/// badges.
+pub struct Day3;
+
+impl Solution for Day3 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        let lines = aoc::parse::lines(input, parse_rucksack)?;
+        let mut sum = 0;
+        for (i, line) in lines.iter().enumerate() {
+            let (first, second) = compartments(line);
+            sum += get_score(the_one(first & second, i + 1, "one item in both compartments")?);
+        }
+
+        Ok(sum.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        Ok(badge_sum(input, 3)?.to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate::generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc::Rng;
+    use proptest::prelude::*;
+    use std::collections::HashSet;
+
+    // What the bitset replaced, to check it against
+    fn hash_misplaced_item(line: &str) -> char {
+        let (first, second) = line.split_at(line.len() / 2);
+        let mut first_set: HashSet<char> = first.chars().collect();
+
+        first_set.retain(|&c| second.contains(c));
+        *first_set.iter().next().unwrap()
+    }
+
+    fn hash_duplicate_item(line1: &str, line2: &str, line3: &str) -> char {
+        let mut first_set: HashSet<char> = line1.chars().collect();
+
+        first_set.retain(|&c| line2.contains(c) && line3.contains(c));
+        *first_set.iter().next().unwrap()
+    }
+
+    fn hash_score(c: char) -> i32 {
+        if c.is_uppercase() {
+            (c as i32) - ('A' as i32) + 27
+        } else {
+            (c as i32) - ('a' as i32) + 1
+        }
+    }
+
+    #[test]
+    fn test_groups() {
+        let sample = include_str!("../sample.txt");
+        let groups = groups(sample, 3).unwrap();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[1].line, 4);
+        assert_eq!(groups[0].badge(), Ok('r'));
+        assert_eq!(groups[1].badge(), Ok('Z'));
+        assert!(groups[0].shared.contains(&('r', 3)));
+        assert!(groups[0].shared.contains(&('F', 2)));
+        assert!(groups[0].to_string().starts_with("    1  "));
+
+        assert_eq!(badge_sum(sample, 1), Err(ParseError::new(1, 1, "one item every rucksack of the group holds", "c, f, g, h, p, r, s, t, v, w, F, J, M, W")));
+        assert_eq!(badge_sum(sample, 6), Err(ParseError::new(1, 1, "one item every rucksack of the group holds", "none")));
+        assert_eq!(
+            badge_sum(sample, 4),
+            Err(ParseError::new(7, 1, "2 more rucksacks for the last group of 4", "end of input"))
+        );
+        assert_eq!(badge_sum("ab\nab\n", 2), Err(ParseError::new(1, 1, "one item every rucksack of the group holds", "a, b")));
+        assert_eq!(
+            Day3.part1("abab\n"),
+            Err(ParseError::new(1, 1, "one item in both compartments", "a, b"))
+        );
+    }
+
+    #[test]
+    fn test_items() {
+        let items = Items::of("vJrwpWtwJgWrhcsFMMfFFhFp");
+        assert_eq!(items.len(), 14);
+        assert_eq!(items.first(), Some('c'));
+        assert_eq!((items & Items::of("aAp")).iter().collect::<String>(), "p");
+        assert_eq!(Items::ALL.len(), 52);
+        assert_eq!(Items::ALL.priority(), (1..=52).sum());
+        assert!(Items::default().is_empty());
+        for item in ITEMS.chars() {
+            assert_eq!(get_score(item), hash_score(item));
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_intersection(a in "[a-zA-Z]{0,30}", b in "[a-zA-Z]{0,30}") {
+            let expected: HashSet<char> = a.chars().filter(|&c| b.contains(c)).collect();
+            let common = Items::of(&a) & Items::of(&b);
+            prop_assert_eq!(common.iter().collect::<HashSet<char>>(), expected.clone());
+            prop_assert_eq!(common.len(), expected.len());
+            prop_assert_eq!(common.priority() as i32, expected.iter().map(|&c| hash_score(c)).sum::<i32>());
+        }
+
+        #[test]
+        fn test_generated_rucksacks(seed in any::<u64>(), size in 1..20usize) {
+            let input = generate::generate(&mut Rng::new(seed), size);
+            let lines: Vec<&str> = input.lines().collect();
+            for line in &lines {
+                prop_assert_eq!(get_misplaced_item(line), hash_misplaced_item(line));
+            }
+            for group in lines.chunks(3) {
+                let badge = get_duplicate_item(group[0], group[1], group[2]);
+                prop_assert_eq!(badge, hash_duplicate_item(group[0], group[1], group[2]));
+            }
+        }
+    }
+}
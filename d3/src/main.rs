@@ -1,42 +1,48 @@
-use std::collections::HashSet;
-use std::io::{self, BufRead};
+use aoc::cli::{self, Format, Options};
+use d3::Day3;
 
-fn get_duplicate_item(line1: &str, line2: &str, line3: &str) -> char {
-    let mut first_set: HashSet<char> = line1.chars().collect();
+const USAGE: &str = "d3 also takes --group <N> to find the badges of groups of N rucksacks instead of 3,
+and --shared to list every item that rucksacks of a group share and how many of them hold it";
 
-    first_set.retain(|&c| line2.contains(c) && line3.contains(c));
-    first_set.iter().next().unwrap().clone()
-}
-
-#[test]
-fn test_get_duplicate_item() {
-    assert_eq!(get_duplicate_item("ab", "ac", "ae"), 'a');
-    assert_eq!(get_duplicate_item("xav", "bqx", "plx"), 'x');
-}
+fn main() {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let shared = args.iter().position(|arg| arg == "--shared").map(|i| args.remove(i));
+    let size = match args.iter().position(|arg| arg == "--group") {
+        Some(i) => {
+            let size = args.get(i + 1).and_then(|size| size.parse::<usize>().ok()).filter(|&size| size > 0);
+            if size.is_none() {
+                eprintln!("--group needs a number above 0\n\n{}", USAGE);
+                std::process::exit(2);
+            }
+            args.drain(i..i + 2);
+            size
+        }
+        None => None,
+    };
 
-fn get_score(c: char) -> i32 {
-    if c.is_uppercase() {
-        (c as i32) - ('A' as i32) + 27
-    } else {
-        (c as i32) - ('a' as i32) + 1
+    let options = Options::from_args(args);
+    if shared.is_none() && size.is_none() {
+        cli::run(3, &Day3, env!("CARGO_MANIFEST_DIR"), &options);
+        return;
     }
-}
 
-fn main() {
-    let lines: Vec<String> = std::io::stdin()
-        .lock()
-        .lines()
-        .map(|line| line.unwrap())
-        .collect();
+    if options.format != Format::Text {
+        eprintln!("--group and --shared only make a text report\n\n{}", USAGE);
+        std::process::exit(2);
+    }
+    let (source, input) = cli::read_input(env!("CARGO_MANIFEST_DIR"), &options);
+    let size = size.unwrap_or(3);
+    let groups = d3::groups(&input, size).unwrap_or_else(|err| cli::fail(&err, &source, &input));
 
     let mut sum = 0;
-    for i in (0..lines.len()).step_by(3) {
-        let line1 = &lines[i];
-        let line2 = &lines[i + 1];
-        let line3 = &lines[i + 2];
-        let duplicate_item = get_duplicate_item(line1, line2, line3);
-        sum += get_score(duplicate_item);
+    for group in &groups {
+        if shared.is_some() {
+            println!("{}", group);
+        }
+        match group.badge() {
+            Ok(badge) => sum += d3::get_score(badge),
+            Err(err) => cli::fail(&err, &source, &input),
+        }
     }
-
-    println!("{}", sum);
+    println!("Badges of {} groups of {}: {}", groups.len(), size, sum);
 }
@@ -0,0 +1,12 @@
+use aoc::Rng;
+
+/// `size` pairs of section assignments.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut range = || {
+        let start = rng.between(1, 99);
+        format!("{}-{}", start, rng.between(start, 99))
+    };
+    (0..size.max(1))
+        .map(|_| format!("{},{}\n", range(), range()))
+        .collect()
+}
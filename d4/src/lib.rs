@@ -0,0 +1,178 @@
+//! Day 4: Camp Cleanup. Compares the pairs of section ranges the elves were
+//! assigned.
+
+use aoc::{Cursor, ParseError, Rng, Solution};
+use std::fmt;
+
+mod generate;
+pub mod sweep;
+
+/// The sections from `start` to `end`, both included. `start` is never after
+/// `end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ElfRange {
+    pub start: i32,
+    pub end: i32,
+}
+
+impl ElfRange {
+    pub fn new(start: i32, end: i32) -> ElfRange {
+        assert!(start <= end, "A range can't end at {} before it starts at {}", end, start);
+        ElfRange { start, end }
+    }
+
+    /// Number of sections in the range.
+    pub fn length(&self) -> i32 {
+        self.end - self.start + 1
+    }
+
+    /// Whether every section of `other` is in this range as well.
+    pub fn contains(&self, other: &ElfRange) -> bool {
+        self.start <= other.start && self.end >= other.end
+    }
+
+    /// The sections in both ranges, if there are any.
+    pub fn intersection(&self, other: &ElfRange) -> Option<ElfRange> {
+        let (start, end) = (self.start.max(other.start), self.end.min(other.end));
+        (start <= end).then_some(ElfRange { start, end })
+    }
+
+    /// Number of sections in both ranges.
+    pub fn overlap(&self, other: &ElfRange) -> i32 {
+        self.intersection(other).map_or(0, |range| range.length())
+    }
+
+    /// The sections in either range: one range when they overlap or touch,
+    /// otherwise both, in order.
+    pub fn union(&self, other: &ElfRange) -> Vec<ElfRange> {
+        let (first, second) = if self <= other { (self, other) } else { (other, self) };
+        if second.start <= first.end + 1 {
+            vec![ElfRange { start: first.start, end: first.end.max(second.end) }]
+        } else {
+            vec![*first, *second]
+        }
+    }
+
+    /// The sections in this range but not in `other`: none, the part on one
+    /// side of it or the parts on both sides, in order.
+    pub fn difference(&self, other: &ElfRange) -> Vec<ElfRange> {
+        let Some(common) = self.intersection(other) else {
+            return vec![*self];
+        };
+        let before = ElfRange { start: self.start, end: common.start - 1 };
+        let after = ElfRange { start: common.end + 1, end: self.end };
+        [before, after].into_iter().filter(|range| range.start <= range.end).collect()
+    }
+}
+
+impl fmt::Display for ElfRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+/// A range written as `start-end`.
+pub fn parse_range(cursor: &mut Cursor) -> Result<ElfRange, ParseError> {
+    let start = cursor.number()?;
+    cursor.tag("-")?;
+    let at = cursor.clone();
+    let end = cursor.number()?;
+    if end < start {
+        return Err(at.error(format!("an end of at least {}", start)));
+    }
+    Ok(ElfRange { start, end })
+}
+
+/// Parses a pair of ranges separated by a comma.
+pub fn parse_line(cursor: &mut Cursor) -> Result<(ElfRange, ElfRange), ParseError> {
+    let a = parse_range(cursor)?;
+    cursor.tag(",")?;
+    let b = parse_range(cursor)?;
+    Ok((a, b))
+}
+
+/// Whether one of the ranges contains the other.
+pub fn contains(a: &ElfRange, b: &ElfRange) -> bool {
+    a.contains(b) || b.contains(a)
+}
+
+/// Whether the ranges have a section in common.
+pub fn intersect(a: &ElfRange, b: &ElfRange) -> bool {
+    a.intersection(b).is_some()
+}
+
+/// Number of lines whose pair of ranges satisfies `predicate`.
+pub fn count_pairs(input: &str, predicate: fn(&ElfRange, &ElfRange) -> bool) -> Result<usize, ParseError> {
+    Ok(aoc::parse::lines(input, parse_line)?
+        .iter()
+        .filter(|(a, b)| predicate(a, b))
+        .count())
+}
+
+/// Part 1 counts the pairs where one range contains the other, part 2 those
+/// that overlap at all.
+pub struct Day4;
+
+impl Solution for Day4 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        Ok(count_pairs(input, contains)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        Ok(count_pairs(input, intersect)?.to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate::generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::BTreeSet;
+
+    fn sections(ranges: &[ElfRange]) -> BTreeSet<i32> {
+        ranges.iter().flat_map(|range| range.start..=range.end).collect()
+    }
+
+    fn range() -> impl Strategy<Value = ElfRange> {
+        (1..30, 0..10).prop_map(|(start, len)| ElfRange::new(start, start + len))
+    }
+
+    #[test]
+    fn test_ranges() {
+        let (a, b) = (ElfRange::new(2, 8), ElfRange::new(3, 7));
+        assert!(a.contains(&b) && !b.contains(&a));
+        assert_eq!(a.intersection(&b), Some(b));
+        assert_eq!(a.difference(&b), vec![ElfRange::new(2, 2), ElfRange::new(8, 8)]);
+        assert_eq!(ElfRange::new(1, 2).union(&ElfRange::new(3, 4)), vec![ElfRange::new(1, 4)]);
+        assert_eq!(ElfRange::new(5, 7).overlap(&ElfRange::new(8, 9)), 0);
+        assert_eq!(a.to_string(), "2-8");
+
+        let mut cursor = Cursor::new(1, "6-4");
+        assert_eq!(parse_range(&mut cursor), Err(ParseError::new(1, 3, "an end of at least 6", "\"4\"")));
+    }
+
+    proptest! {
+        // Each operation gives the sections of the set operation it's named after
+        #[test]
+        fn test_algebra(a in range(), b in range()) {
+            let (sa, sb) = (sections(&[a]), sections(&[b]));
+            let common: BTreeSet<i32> = sa.intersection(&sb).copied().collect();
+
+            prop_assert_eq!(a.length() as usize, sa.len());
+            prop_assert_eq!(sections(&a.intersection(&b).into_iter().collect::<Vec<_>>()), common.clone());
+            prop_assert_eq!(a.overlap(&b) as usize, common.len());
+            prop_assert_eq!(sections(&a.union(&b)), sa.union(&sb).copied().collect());
+            prop_assert_eq!(sections(&a.difference(&b)), sa.difference(&sb).copied().collect());
+            prop_assert_eq!(a.contains(&b), sb.is_subset(&sa));
+
+            // The ranges a union or difference gives don't touch each other
+            for ranges in [a.union(&b), a.difference(&b)] {
+                prop_assert!(ranges.windows(2).all(|pair| pair[0].end + 1 < pair[1].start));
+            }
+        }
+    }
+}
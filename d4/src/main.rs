@@ -1,48 +1,60 @@
-use std::io::{self, BufRead};
+use aoc::cli::{self, Format, Options};
+use d4::{sweep, Day4, ElfRange};
 
-#[derive(Debug)]
-struct ElfRange {
-    start: i32,
-    end: i32,
-}
-fn parse_range(s: &str) -> ElfRange {
-    let mut parts = s.split('-');
-    let start = parts.next().unwrap().parse().unwrap();
-    let end = parts.next().unwrap().parse().unwrap();
-    ElfRange { start, end }
-}
+const USAGE: &str = "d4 also takes --overlaps to show how many sections each pair shares, with both counts,
+and --sweep to find every two elves of any pairs whose sections overlap, the sections nobody covers
+and those three or more elves cover";
 
-// Parses a pair of ranges separated by a comma.
-fn parse_line(s: &str) -> (ElfRange, ElfRange) {
-    let mut parts = s.split(',');
-    let a = parse_range(parts.next().unwrap());
-    let b = parse_range(parts.next().unwrap());
-    (a, b)
-}
+fn main() {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let overlaps = args.iter().position(|arg| arg == "--overlaps").map(|i| args.remove(i));
+    let sweep = args.iter().position(|arg| arg == "--sweep").map(|i| args.remove(i));
 
-fn contains(a: &ElfRange, b: &ElfRange) -> bool {
-    fn contains_impl(a: &ElfRange, b: &ElfRange) -> bool {
-        a.start <= b.start && a.end >= b.end
+    let options = Options::from_args(args);
+    if overlaps.is_none() && sweep.is_none() {
+        cli::run(4, &Day4, env!("CARGO_MANIFEST_DIR"), &options);
+        return;
+    }
+
+    if options.format != Format::Text {
+        eprintln!("--overlaps and --sweep only make a text breakdown\n\n{}", USAGE);
+        std::process::exit(2);
+    }
+    let (source, input) = cli::read_input(env!("CARGO_MANIFEST_DIR"), &options);
+    let pairs = aoc::parse::lines(&input, d4::parse_line).unwrap_or_else(|err| cli::fail(&err, &source, &input));
+
+    if sweep.is_some() {
+        report_sweep(&pairs);
+        return;
     }
-    return contains_impl(a, b) || contains_impl(b, a);
-}
 
-fn intersect(a: &ElfRange, b: &ElfRange) -> bool {
-    fn intersect_impl(a: &ElfRange, b: &ElfRange) -> bool {
-        a.start <= b.start && a.end >= b.start
+    for (line, (a, b)) in pairs.iter().enumerate() {
+        let pair = format!("{},{}", a, b);
+        println!("{:5} {:12} overlap {}", line + 1, pair, a.overlap(b));
     }
-    return intersect_impl(a, b) || intersect_impl(b, a);
+    let count = |predicate: fn(&ElfRange, &ElfRange) -> bool| pairs.iter().filter(|(a, b)| predicate(a, b)).count();
+    println!("Containing {}  overlapping {}", count(d4::contains), count(d4::intersect));
 }
 
-fn main() {
-    // reads all lines  and parses them
-    let answer = std::io::stdin()
-        .lock()
-        .lines()
-        .map(|line| line.unwrap())
-        .map(|line| parse_line(&line))
-        .filter(|(a, b)| intersect(&a, &b))
-        .count();
-
-    println!("{}", answer);
+fn report_sweep(pairs: &[(ElfRange, ElfRange)]) {
+    let assignments = sweep::assignments(pairs);
+    let overlapping = sweep::overlapping_pairs(&assignments);
+    for (a, b) in &overlapping {
+        println!("{} {}", a, b);
+    }
+    println!("{} pairs of elves overlap", overlapping.len());
+
+    let ranges = assignments.iter().map(|&(_, range)| range);
+    let runs = sweep::coverage(ranges.clone(), sweep::sections(ranges));
+    let uncovered: Vec<String> = sweep::sections_where(&runs, |count| count == 0)
+        .iter()
+        .map(|(range, _)| range.to_string())
+        .collect();
+    let crowded: Vec<String> = sweep::sections_where(&runs, |count| count >= 3)
+        .iter()
+        .map(|(range, most)| format!("{} (up to {})", range, most))
+        .collect();
+    let list = |ranges: Vec<String>| if ranges.is_empty() { "none".to_string() } else { ranges.join(", ") };
+    println!("Nobody covers: {}", list(uncovered));
+    println!("Three or more cover: {}", list(crowded));
 }
@@ -0,0 +1,190 @@
+//! Compares every elf's range with every other one, not only its partner's,
+//! by sweeping over the ends of the ranges in order.
+
+use crate::ElfRange;
+use std::collections::BTreeSet;
+use std::fmt;
+
+/// An elf, as the line of its pair and whether it's first or second there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Elf {
+    pub line: usize,
+    pub position: usize,
+}
+
+impl fmt::Display for Elf {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}", self.line, self.position)
+    }
+}
+
+/// Every elf of the pairs with its range.
+pub fn assignments(pairs: &[(ElfRange, ElfRange)]) -> Vec<(Elf, ElfRange)> {
+    pairs
+        .iter()
+        .enumerate()
+        .flat_map(|(i, (a, b))| {
+            let elf = |position| Elf { line: i + 1, position };
+            [(elf(1), *a), (elf(2), *b)]
+        })
+        .collect()
+}
+
+/// Every two elves whose ranges have a section in common, in order.
+///
+/// A range opens at its start and closes after its end, so at one section
+/// all openings come before the closings. Each range that opens overlaps the
+/// ones still open, which makes this O(n log n) plus the number of pairs.
+pub fn overlapping_pairs(assignments: &[(Elf, ElfRange)]) -> Vec<(Elf, Elf)> {
+    let mut events: Vec<(i32, bool, usize)> = Vec::new();
+    for (i, (_, range)) in assignments.iter().enumerate() {
+        events.push((range.start, false, i));
+        events.push((range.end, true, i));
+    }
+    events.sort_unstable();
+
+    let mut open: BTreeSet<usize> = BTreeSet::new();
+    let mut pairs = Vec::new();
+    for (_, closing, i) in events {
+        if closing {
+            open.remove(&i);
+            continue;
+        }
+        let elf = assignments[i].0;
+        for &j in &open {
+            let other = assignments[j].0;
+            pairs.push((elf.min(other), elf.max(other)));
+        }
+        open.insert(i);
+    }
+
+    pairs.sort_unstable();
+    pairs
+}
+
+/// How many ranges cover each section of `sections`, as runs of sections
+/// covered equally often. The runs reach further where a range sticks out.
+pub fn coverage(ranges: impl IntoIterator<Item = ElfRange>, sections: ElfRange) -> Vec<(ElfRange, usize)> {
+    let mut changes: Vec<(i32, i32)> = Vec::new();
+    for range in ranges {
+        changes.push((range.start, 1));
+        changes.push((range.end + 1, -1));
+    }
+    changes.sort_unstable();
+
+    let mut runs: Vec<(ElfRange, usize)> = Vec::new();
+    let mut covering = 0;
+    let mut changes = changes.into_iter().peekable();
+    while let Some((section, change)) = changes.next() {
+        covering += change;
+        let Some(&(next, _)) = changes.peek() else { break };
+        if next == section {
+            continue;
+        }
+        match runs.last_mut() {
+            // A range that starts right after another one ends changes nothing
+            Some((run, count)) if *count == covering as usize => run.end = next - 1,
+            _ => runs.push((ElfRange::new(section, next - 1), covering as usize)),
+        }
+    }
+
+    // Nobody covers the sections before the first range or after the last
+    let (first, last) = match (runs.first(), runs.last()) {
+        (Some(first), Some(last)) => (first.0.start, last.0.end),
+        _ => return vec![(sections, 0)],
+    };
+    if sections.start < first {
+        runs.insert(0, (ElfRange::new(sections.start, first - 1), 0));
+    }
+    if last < sections.end {
+        runs.push((ElfRange::new(last + 1, sections.end), 0));
+    }
+    runs
+}
+
+/// The sections of the camp as far as the assignments tell: from 1, or from
+/// lower if a range starts there, up to the highest end.
+pub fn sections(ranges: impl IntoIterator<Item = ElfRange>) -> ElfRange {
+    ranges
+        .into_iter()
+        .fold(ElfRange::new(1, 1), |all, range| ElfRange::new(all.start.min(range.start), all.end.max(range.end)))
+}
+
+/// The sections covered a number of times `keep` accepts, as ranges of
+/// adjacent runs with the most times any of their sections is covered.
+pub fn sections_where(runs: &[(ElfRange, usize)], keep: impl Fn(usize) -> bool) -> Vec<(ElfRange, usize)> {
+    let mut merged: Vec<(ElfRange, usize)> = Vec::new();
+    for &(run, count) in runs.iter().filter(|&&(_, count)| keep(count)) {
+        match merged.last_mut() {
+            Some((range, most)) if range.end + 1 == run.start => {
+                range.end = run.end;
+                *most = (*most).max(count);
+            }
+            _ => merged.push((run, count)),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_sweep() {
+        let pairs = aoc::parse::lines(include_str!("../sample.txt"), crate::parse_line).unwrap();
+        let assignments = assignments(&pairs);
+        let elf = |line, position| Elf { line, position };
+
+        let overlapping = overlapping_pairs(&assignments);
+        assert_eq!(overlapping.len(), 49);
+        assert!(overlapping.contains(&(elf(1, 1), elf(2, 1))));
+        assert!(!overlapping.contains(&(elf(1, 1), elf(1, 2))));
+
+        let ranges = [ElfRange::new(2, 4), ElfRange::new(3, 5), ElfRange::new(8, 8)];
+        assert_eq!(sections(ranges), ElfRange::new(1, 8));
+        let runs = coverage(ranges, ElfRange::new(1, 10));
+        let expected = [((1, 1), 0), ((2, 2), 1), ((3, 4), 2), ((5, 5), 1), ((6, 7), 0), ((8, 8), 1), ((9, 10), 0)];
+        let expected: Vec<_> = expected.iter().map(|&((start, end), count)| (ElfRange::new(start, end), count)).collect();
+        assert_eq!(runs, expected);
+        let uncovered = [(ElfRange::new(1, 1), 0), (ElfRange::new(6, 7), 0), (ElfRange::new(9, 10), 0)];
+        assert_eq!(sections_where(&runs, |count| count == 0), uncovered);
+        assert_eq!(coverage([], ElfRange::new(1, 3)), vec![(ElfRange::new(1, 3), 0)]);
+        assert_eq!(sections_where(&runs, |count| count >= 1), vec![(ElfRange::new(2, 5), 2), (ElfRange::new(8, 8), 1)]);
+    }
+
+    proptest! {
+        // The sweep finds the same pairs as comparing every two elves, and
+        // the runs count the ranges over each section
+        #[test]
+        fn test_against_brute_force(ranges in prop::collection::vec((1..40, 0..8), 0..30)) {
+            let ranges: Vec<ElfRange> = ranges.iter().map(|&(start, len)| ElfRange::new(start, start + len)).collect();
+            let pairs: Vec<(ElfRange, ElfRange)> = ranges.chunks_exact(2).map(|pair| (pair[0], pair[1])).collect();
+            let assignments = assignments(&pairs);
+
+            let mut expected = Vec::new();
+            for (i, (a, range_a)) in assignments.iter().enumerate() {
+                for (b, range_b) in &assignments[i + 1..] {
+                    if range_a.intersection(range_b).is_some() {
+                        expected.push((*a.min(b), *a.max(b)));
+                    }
+                }
+            }
+            expected.sort_unstable();
+            prop_assert_eq!(overlapping_pairs(&assignments), expected);
+
+            let all = sections(ranges.iter().copied());
+            let runs = coverage(assignments.iter().map(|&(_, range)| range), all);
+            prop_assert_eq!(runs.first().map(|(run, _)| run.start), Some(all.start));
+            prop_assert_eq!(runs.last().map(|(run, _)| run.end), Some(all.end));
+            for (run, count) in &runs {
+                for section in run.start..=run.end {
+                    let covering = assignments.iter().filter(|(_, range)| range.start <= section && section <= range.end).count();
+                    prop_assert_eq!(covering, *count);
+                }
+            }
+            prop_assert!(runs.windows(2).all(|pair| pair[0].0.end + 1 == pair[1].0.start && pair[0].1 != pair[1].1));
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use crate::{drawing, Stack};
+use aoc::Rng;
+
+/// Up to nine stacks of crates and `size` moves. The moves never empty a
+/// stack, so every stack has a crate on top at the end.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let count = rng.between(2, 9) as usize;
+    let mut stacks: Vec<Vec<char>> = (0..count)
+        .map(|_| {
+            (0..rng.between(1, 8))
+                .map(|_| (b'A' + rng.below(26) as u8) as char)
+                .collect()
+        })
+        .collect();
+    if stacks.iter().all(|stack| stack.len() == 1) {
+        stacks[0].push('A');
+    }
+
+    let labels: Vec<Stack> = stacks.iter().map(|stack| stack.iter().map(char::to_string).collect()).collect();
+    let mut output = drawing(&labels);
+    output.push('\n');
+
+    for _ in 0..size.max(1) {
+        let candidates: Vec<usize> = (0..count).filter(|&i| stacks[i].len() > 1).collect();
+        let from = *rng.pick(&candidates);
+        let to = (from + 1 + rng.below(count - 1)) % count;
+        let moved = rng.between(1, stacks[from].len() as i64 - 1) as usize;
+
+        let split = stacks[from].len() - moved;
+        let crates = stacks[from].split_off(split);
+        stacks[to].extend(crates);
+
+        output += &format!("move {} from {} to {}\n", moved, from + 1, to + 1);
+    }
+
+    output
+}
@@ -0,0 +1,415 @@
+//! Day 5: Supply Stacks. Replays the crane's moves on the stacks of crates.
+
+use aoc::visual::text_frame;
+use aoc::{Cursor, ParseError, Recorder, Rng, Solution};
+use std::fmt;
+
+mod generate;
+
+/// A crate, by its label: a letter in the puzzle, but any run of letters
+/// and digits will do.
+pub type Crate = String;
+
+/// The crates of a stack, bottom crate first.
+pub type Stack = Vec<Crate>;
+
+/// Where the numbers of the stacks are in the bottom line of the drawing, as
+/// the first and last column of each.
+pub fn parse_stack_numbers(cursor: &mut Cursor) -> Result<Vec<(usize, usize)>, ParseError> {
+    let mut columns = Vec::new();
+    loop {
+        cursor.skip_whitespace();
+        if cursor.is_empty() && !columns.is_empty() {
+            return Ok(columns);
+        }
+        let start = cursor.clone();
+        let number: usize = cursor.number()?;
+        if number != columns.len() + 1 {
+            return Err(start.error(format!("stack {}", columns.len() + 1)));
+        }
+        columns.push((start.column(), cursor.column() - 1));
+    }
+}
+
+/// The crates of a line of the drawing, each with the stack whose number it
+/// is above. There's one crate per stack at most.
+pub fn parse_crate_line(cursor: &mut Cursor, columns: &[(usize, usize)]) -> Result<Vec<(usize, Crate)>, ParseError> {
+    let mut crates: Vec<(usize, Crate)> = Vec::new();
+    loop {
+        cursor.skip_whitespace();
+        if cursor.is_empty() {
+            return Ok(crates);
+        }
+        let start = cursor.clone();
+        cursor.tag("[")?;
+        let label = cursor.word()?;
+        cursor.tag("]")?;
+
+        let (left, right) = (start.column(), cursor.column() - 1);
+        let stack = columns
+            .iter()
+            .position(|&(first, last)| first <= right && left <= last)
+            .ok_or_else(|| start.error("a crate above the number of its stack"))?;
+        if crates.last().is_some_and(|&(last, _)| last >= stack) {
+            return Err(start.error(format!("a single crate above stack {}", stack + 1)));
+        }
+        crates.push((stack, label.to_string()));
+    }
+}
+
+
+/// The stacks the way the puzzle draws them, crates as `[Z]` above a line
+/// with the number of each stack. Every stack is as wide as its widest crate
+/// or number, so the drawing parses back to the same stacks.
+pub fn drawing(stacks: &[Stack]) -> String {
+    let widths: Vec<usize> = stacks
+        .iter()
+        .enumerate()
+        .map(|(i, stack)| {
+            let widest = stack.iter().map(|label| label.chars().count() + 2).max().unwrap_or(3);
+            widest.max((i + 1).to_string().len())
+        })
+        .collect();
+    let line = |cells: Vec<String>| cells.join(" ").trim_end().to_string() + "\n";
+
+    let height = stacks.iter().map(|stack| stack.len()).max().unwrap_or(0);
+    let mut output = String::new();
+    for level in (0..height).rev() {
+        let cells = stacks.iter().zip(&widths).map(|(stack, &width)| match stack.get(level) {
+            Some(label) => format!("{:^width$}", format!("[{}]", label)),
+            None => " ".repeat(width),
+        });
+        output += &line(cells.collect());
+    }
+    let numbers = widths.iter().enumerate().map(|(i, &width)| format!("{:^width$}", i + 1));
+    output + &line(numbers.collect())
+}
+
+/// Moves `count` crates from stack `from` to stack `to`, counting stacks from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Command {
+    pub from: usize,
+    pub to: usize,
+    pub count: usize,
+}
+
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "move {} from {} to {}", self.count, self.from, self.to)
+    }
+}
+
+
+/*
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2
+ */
+/// A move between two of the `stacks` stacks.
+pub fn parse_command_line(cursor: &mut Cursor, stacks: usize) -> Result<Command, ParseError> {
+    let stack = |cursor: &mut Cursor| {
+        let start = cursor.clone();
+        match cursor.number()? {
+            n if (1..=stacks).contains(&n) => Ok(n),
+            _ => Err(start.error(format!("a stack from 1 to {}", stacks))),
+        }
+    };
+
+    cursor.tag("move ")?;
+    let start = cursor.clone();
+    let count = cursor.number()?;
+    if count == 0 {
+        return Err(start.error("at least one crate"));
+    }
+    cursor.tag(" from ")?;
+    let from = stack(cursor)?;
+    cursor.tag(" to ")?;
+    let start = cursor.clone();
+    let to = stack(cursor)?;
+    if to == from {
+        return Err(start.error(format!("a stack other than {}", from)));
+    }
+    Ok(Command { from, to, count })
+}
+
+#[test]
+fn parse_command_line_test() {
+    let command = parse_command_line(&mut Cursor::new(1, "move 1 from 2 to 1"), 9);
+    assert_eq!(command, Ok(Command { from: 2, to: 1, count: 1 }));
+    let command = parse_command_line(&mut Cursor::new(1, "move 17 from 5 to 9"), 9);
+    assert_eq!(command, Ok(Command { from: 5, to: 9, count: 17 }));
+    let command = parse_command_line(&mut Cursor::new(4, "move 17 from 5 to 10"), 9);
+    assert_eq!(command, Err(ParseError::new(4, 19, "a stack from 1 to 9", "\"10\"")));
+    let command = parse_command_line(&mut Cursor::new(1, "move 2 from 3 to 3"), 9);
+    assert_eq!(command, Err(ParseError::new(1, 18, "a stack other than 3", "\"3\"")));
+    let command = parse_command_line(&mut Cursor::new(1, "move 0 from 1 to 3"), 9);
+    assert_eq!(command, Err(ParseError::new(1, 6, "at least one crate", "\"0\"")));
+}
+
+
+fn get_two_elements_mut<T>(v: &mut Vec<T>, i: usize, j: usize) -> (&mut T, &mut T) {
+    if i > j {
+        let (a, b) = get_two_elements_mut(v, j, i);
+        return (b, a);
+    }
+
+    let (first, rest) = v.split_at_mut(i+1);
+    (&mut first[i], &mut rest[j-i-1])
+}
+
+#[test]
+fn test_drawing() {
+    let sample = include_str!("../sample.txt");
+    let procedure = parse_input(sample).unwrap();
+    assert!(sample.starts_with(&drawing(&procedure.stacks)));
+
+    let wide = vec![vec!["AB".to_string()], vec![], vec!["C".to_string(); 10]];
+    let drawn = drawing(&wide);
+    assert!(drawn.ends_with("         [C]\n[AB]     [C]\n 1    2   3\n"));
+    assert_eq!(parse_input(&(drawn + "\n")).unwrap().stacks, wide);
+
+    // Every step of a replay draws the way it parses
+    let mut steps = 0;
+    let end = replay(&procedure, Crane::CrateMover9000, |line, command, stacks| {
+        steps += 1;
+        let redrawn = format!("{}\n{}\n", drawing(stacks), command);
+        assert_eq!(parse_input(&redrawn).unwrap().stacks, stacks, "after line {}", line);
+        steps < 3
+    });
+    assert_eq!(steps, 3);
+    assert_eq!(top_crates(&end.unwrap()), "MZ");
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    // Any stacks, with labels of any length and stacks to well past nine,
+    // draw the way they parse
+    #[test]
+    fn test_drawing_parses_back(stacks in proptest::collection::vec(
+        proptest::collection::vec("[A-Z0-9]{1,4}", 0..6), 1..14,
+    )) {
+        let drawn = drawing(&stacks) + "\n";
+        proptest::prop_assert_eq!(parse_input(&drawn).unwrap().stacks, stacks);
+    }
+}
+
+#[test]
+fn test_cranes() {
+    let sample = include_str!("../sample.txt");
+    assert_eq!(rearrange(sample, Crane::CrateMover9000), Ok("CMZ".to_string()));
+    assert_eq!(rearrange(sample, Crane::CrateMover9001), Ok("MCD".to_string()));
+
+    let command = Command { from: 1, to: 2, count: 2 };
+    let stacks = || vec![vec!["A".to_string(), "B".to_string(), "C".to_string()], vec![]];
+    let mut moved = stacks();
+    command.execute(Crane::CrateMover9000, &mut moved);
+    assert_eq!(top_crates(&moved), "AB");
+    let mut moved = stacks();
+    command.execute(Crane::CrateMover9001, &mut moved);
+    assert_eq!(top_crates(&moved), "AC");
+    assert!(Command { from: 2, to: 1, count: 1 }.check(&stacks(), 7).is_err());
+
+    // Commands built by hand are checked like the parsed ones
+    let check = |from, to, count| Command { from, to, count }.check(&stacks(), 7);
+    assert_eq!(check(1, 2, 3), Ok(()));
+    assert_eq!(check(0, 2, 1), Err(ParseError::new(7, 13, "a stack from 1 to 2", "\"0\"")));
+    assert_eq!(check(1, 3, 1), Err(ParseError::new(7, 18, "a stack from 1 to 2", "\"3\"")));
+    assert_eq!(check(1, 1, 1), Err(ParseError::new(7, 18, "a stack other than 1", "\"1\"")));
+    assert_eq!(check(1, 2, 10), Err(ParseError::new(7, 6, "at most the 3 crates of stack 1", "\"10\"")));
+}
+
+#[test]
+fn test_parse_input() {
+    let procedure = parse_input(include_str!("../sample.txt")).unwrap();
+    assert_eq!(procedure.stacks, vec![vec!["Z", "N"], vec!["M", "C", "D"], vec!["P"]]);
+    assert_eq!(procedure.commands[0], (6, Command { from: 2, to: 1, count: 1 }));
+
+    // Wide stacks, labels longer than a letter and more than nine stacks
+    let wide = "[AB]                                       [K]\n[CD] [E] [F] [G] [H] [I] [J] [X] [Y] [Z] [L]\n 1    2   3   4   5   6   7   8   9   10  11\n\nmove 2 from 1 to 10\n";
+    let Procedure { stacks, commands } = parse_input(wide).unwrap();
+    assert_eq!(stacks.len(), 11);
+    assert_eq!(stacks[0], vec!["CD", "AB"]);
+    assert_eq!(stacks[10], vec!["L", "K"]);
+    assert_eq!(commands[0].1, Command { from: 1, to: 10, count: 2 });
+    // Stack 1 ends up empty, so it has no crate on top
+    assert_eq!(rearrange(wide, Crane::CrateMover9001), Ok("EFGHIJXYABK".to_string()));
+
+    let error = |input: &str| parse_input(input).unwrap_err();
+    assert_eq!(error("[A]\n 1   3\n\n"), ParseError::new(2, 6, "stack 2", "\"3\""));
+    assert_eq!(error("    [A]\n[B]\n 1   2\n\n"), ParseError::new(2, 6, "a crate below the one above stack 2", "nothing"));
+    assert_eq!(error("  [A]\n 1   2\n\n"), ParseError::new(1, 3, "a crate above the number of its stack", "\"[A]\""));
+    assert_eq!(
+        rearrange("[A]\n 1   2\n\nmove 2 from 1 to 2\n", Crane::CrateMover9000),
+        Err(ParseError::new(4, 6, "at most the 1 crates of stack 1", "\"2\""))
+    );
+}
+
+#[test]
+fn test_get_two_elements_mut() {
+    let mut v = vec![1, 2, 3, 4, 5];
+    let (a, b) = get_two_elements_mut(&mut v, 1, 3);
+    *a = 10;
+    *b = 20;
+    assert_eq!(v, vec![1, 10, 3, 20, 5]);
+
+    let (a, b) = get_two_elements_mut(&mut v, 3, 1);
+    *a = 10;
+    *b = 20;
+    assert_eq!(v, vec![1, 20, 3, 10, 5]);
+}
+
+/// The stacks of the drawing and the moves below it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Procedure {
+    pub stacks: Vec<Stack>,
+    /// Every move with its line
+    pub commands: Vec<(usize, Command)>,
+}
+
+/// Parses the drawing of the stacks, the empty line and the moves.
+pub fn parse_input(input: &str) -> Result<Procedure, ParseError> {
+    let lines: Vec<&str> = input.lines().collect();
+
+    // find first line that is empty
+    let split_point = match lines.iter().position(|line| line.trim().is_empty()) {
+        Some(0) => return Err(ParseError::new(1, 1, "a drawing of the stacks", "an empty line")),
+        Some(split_point) => split_point,
+        None => return Err(ParseError::end_of_input(input, "an empty line after the stacks")),
+    };
+    let mut cursors: Vec<Cursor> = aoc::parse::cursors(input).take(split_point).collect();
+    let mut numbers = cursors.pop().unwrap();
+    let columns = parse_stack_numbers(&mut numbers)?;
+
+    // From the bottom up, so a crate with nothing below it shows as one
+    let mut crates: Vec<Stack> = vec![Vec::new(); columns.len()];
+    for (row, mut cursor) in cursors.into_iter().rev().enumerate() {
+        let line = cursor.line();
+        for (stack, label) in parse_crate_line(&mut cursor, &columns)? {
+            if crates[stack].len() < row {
+                let expected = format!("a crate below the one above stack {}", stack + 1);
+                return Err(ParseError::new(line + 1, columns[stack].0, expected, "nothing"));
+            }
+            crates[stack].push(label);
+        }
+    }
+
+    let mut commands = Vec::new();
+    for mut cursor in aoc::parse::cursors(input).skip(split_point + 1) {
+        let command = parse_command_line(&mut cursor, columns.len())?;
+        cursor.end()?;
+        commands.push((cursor.line(), command));
+    }
+
+    Ok(Procedure { stacks: crates, commands })
+}
+
+/// The crane that makes the moves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Crane {
+    /// Lifts a single crate per step, so the moved crates end up in reverse
+    /// order
+    CrateMover9000,
+    /// Lifts all crates of a move at once, keeping their order
+    CrateMover9001,
+}
+
+impl Command {
+    /// Fails when the command names a stack that isn't there, moves crates
+    /// onto the stack they come from or moves more crates than there are.
+    /// The command is on line `line`, written the way `Display` writes it.
+    pub fn check(&self, stacks: &[Stack], line: usize) -> Result<(), ParseError> {
+        let from_column = 12 + self.count.to_string().len();
+        let to_column = from_column + self.from.to_string().len() + 4;
+        let found = |stack: usize| format!("\"{}\"", stack);
+        let stack_range = format!("a stack from 1 to {}", stacks.len());
+        if !(1..=stacks.len()).contains(&self.from) {
+            return Err(ParseError::new(line, from_column, stack_range, found(self.from)));
+        }
+        if !(1..=stacks.len()).contains(&self.to) {
+            return Err(ParseError::new(line, to_column, stack_range, found(self.to)));
+        }
+        if self.from == self.to {
+            return Err(ParseError::new(line, to_column, format!("a stack other than {}", self.from), found(self.to)));
+        }
+
+        let crates = stacks[self.from - 1].len();
+        if self.count > crates {
+            let expected = format!("at most the {} crates of stack {}", crates, self.from);
+            return Err(ParseError::new(line, 6, expected, format!("\"{}\"", self.count)));
+        }
+        Ok(())
+    }
+
+    /// Moves the crates between `stacks` the way `crane` does. The command
+    /// has to pass [`Command::check`].
+    pub fn execute(&self, crane: Crane, stacks: &mut Vec<Stack>) {
+        let (from, to) = get_two_elements_mut(stacks, self.from - 1, self.to - 1);
+
+        let mut moved = from.split_off(from.len() - self.count);
+        if crane == Crane::CrateMover9000 {
+            moved.reverse();
+        }
+        to.append(&mut moved);
+    }
+}
+
+/// The labels of the crates on top of the stacks. Empty stacks add nothing.
+pub fn top_crates(stacks: &[Stack]) -> String {
+    stacks.iter().filter_map(|stack| stack.last()).map(String::as_str).collect()
+}
+
+/// Makes the moves of `procedure` with `crane` and hands the stacks after
+/// each one to `on_step`, with the move and its line. The replay stops early
+/// when `on_step` returns false. Returns the stacks it ends with.
+pub fn replay(
+    procedure: &Procedure,
+    crane: Crane,
+    mut on_step: impl FnMut(usize, &Command, &[Stack]) -> bool,
+) -> Result<Vec<Stack>, ParseError> {
+    let mut stacks = procedure.stacks.clone();
+
+    for (line, command) in &procedure.commands {
+        command.check(&stacks, *line)?;
+        command.execute(crane, &mut stacks);
+        if !on_step(*line, command, &stacks) {
+            break;
+        }
+    }
+
+    Ok(stacks)
+}
+
+/// The crates on top of the stacks after `crane` made all moves.
+pub fn rearrange(input: &str, crane: Crane) -> Result<String, ParseError> {
+    let stacks = replay(&parse_input(input)?, crane, |_, _, _| true)?;
+    Ok(top_crates(&stacks))
+}
+
+/// Part 1 is moved by the CrateMover 9000, part 2 by the CrateMover 9001.
+pub struct Day5;
+
+impl Solution for Day5 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        rearrange(input, Crane::CrateMover9000)
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        rearrange(input, Crane::CrateMover9001)
+    }
+
+    // The drawing before the first move and after every one of part 1
+    fn record(&self, input: &str, recorder: &mut Recorder) -> Result<(), ParseError> {
+        let procedure = parse_input(input)?;
+        recorder.capture(|| text_frame(&drawing(&procedure.stacks)));
+        replay(&procedure, Crane::CrateMover9000, |_, command, stacks| {
+            recorder.capture(|| text_frame(&format!("{}\n{}", drawing(stacks), command)));
+            true
+        })?;
+        Ok(())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate::generate(rng, size)
+    }
+}
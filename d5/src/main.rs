@@ -1,128 +1,94 @@
-use std::io::BufRead;
+use aoc::cli::{self, Format, Options};
+use aoc::input::Source;
+use d5::{Crane, Day5, Procedure};
+use std::io::{self, BufRead, Write};
 
-fn parse_crate_line(line: &str, num: usize) -> Vec<Option<char>> {
-    let chars = line.chars().collect::<Vec<char>>();
-    let mut result: Vec<Option<char>> = Vec::new();
+const USAGE: &str = "d5 also takes --crane <9000|9001> to pick the crane, --after <N> to draw the stacks after the
+Nth move, --trace to draw them after every move, and --replay to step through the moves with Enter
+(q to stop), which needs the input from --input <PATH> or --sample since it reads the keys from stdin";
 
-    for i in (1..num*4).step_by(4) {
-        if i > chars.len() {
-            result.push(None);
-        } else {
-            let c = chars[i];
-            if c == ' ' {
-                result.push(None);
-            } else {
-                result.push(Some(c));
-            }
-        }
+fn main() {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let trace = args.iter().position(|arg| arg == "--trace").map(|i| args.remove(i));
+    let replay = args.iter().position(|arg| arg == "--replay").map(|i| args.remove(i));
+    let crane = value(&mut args, "--crane", "9000 or 9001", |crane| match crane {
+        "9000" => Some(Crane::CrateMover9000),
+        "9001" => Some(Crane::CrateMover9001),
+        _ => None,
+    });
+    let after = value(&mut args, "--after", "a number", |after| after.parse::<usize>().ok());
+
+    let options = Options::from_args(args);
+    if trace.is_none() && replay.is_none() && crane.is_none() && after.is_none() {
+        cli::run(5, &Day5, env!("CARGO_MANIFEST_DIR"), &options);
+        return;
     }
 
-    result
-}
-
-
-#[derive(Debug, PartialEq, Eq)]
-struct Command {
-    from: usize,
-    to: usize,
-    count: usize,
-}
-
-
-/*
-move 1 from 2 to 1
-move 3 from 1 to 3
-move 2 from 2 to 1
-move 1 from 1 to 2
- */
-fn parse_command_line(line: &str) -> Command {
-    let mut parts = line.split_whitespace();
-    parts.next();
-    let count = parts.next().unwrap().parse::<usize>().unwrap();
-    parts.next();
-    let from = parts.next().unwrap().parse::<usize>().unwrap();
-    parts.next();
-    let to = parts.next().unwrap().parse::<usize>().unwrap();
-    Command { from, to, count }
-}
-
-#[test]
-fn parse_command_line_test() {
-    let command = parse_command_line("move 1 from 2 to 1");
-    assert_eq!(command, Command { from: 2, to: 1, count: 1 });
-    let command = parse_command_line("move 17 from 5 to 9");
-    assert_eq!(command, Command { from: 5, to: 9, count: 17 });
-}
-
-
-fn get_two_elements_mut<'a, T>(v: &'a mut Vec<T>, i: usize, j: usize) -> (&'a mut T, &'a mut T) {
-    if i > j {
-        let (a, b) = get_two_elements_mut(v, j, i);
-        return (b, a);
+    if options.format != Format::Text {
+        eprintln!("--crane, --after, --trace and --replay only make a text drawing\n\n{}", USAGE);
+        std::process::exit(2);
+    }
+    if replay.is_some() && options.source == Source::Stdin {
+        eprintln!("--replay reads the keys from stdin, so the input has to come from elsewhere\n\n{}", USAGE);
+        std::process::exit(2);
+    }
+    let (source, input) = cli::read_input(env!("CARGO_MANIFEST_DIR"), &options);
+    let procedure = d5::parse_input(&input).unwrap_or_else(|err| cli::fail(&err, &source, &input));
+    let crane = crane.unwrap_or(Crane::CrateMover9000);
+    if let Some(after) = after.filter(|&after| after > procedure.commands.len()) {
+        eprintln!("--after {} is past the last of the {} moves", after, procedure.commands.len());
+        std::process::exit(2);
     }
 
-    let (first, rest) = v.split_at_mut(i+1);
-    (&mut first[i], &mut rest[j-i-1])
-}
-
-#[test]
-fn test_get_two_elements_mut() {
-    let mut v = vec![1, 2, 3, 4, 5];
-    let (a, b) = get_two_elements_mut(&mut v, 1, 3);
-    *a = 10;
-    *b = 20;
-    assert_eq!(v, vec![1, 10, 3, 20, 5]);
-
-    let (a, b) = get_two_elements_mut(&mut v, 3, 1);
-    *a = 10;
-    *b = 20;
-    assert_eq!(v, vec![1, 20, 3, 10, 5]);
-}
-
-fn main() {
-    let lines: Vec<String> = std::io::stdin()
-        .lock()
-        .lines()
-        .map(|line| line.unwrap())
-        .collect();
-
-    // find first line that is empty
-    let split_point = lines.iter().position(|line| line.is_empty()).unwrap();
-    let (crates_lines, command_lines) = lines.split_at(split_point+1);
-
-    let mut crates_lines: Vec<_> = crates_lines.iter().collect();
-    crates_lines.pop();
-    crates_lines.pop();
-    let num_crates = lines[split_point-1].chars().count()/4 + 1;
-
-    let mut crates: Vec<Vec<char>> = Vec::new();
-    crates.resize(num_crates, Vec::new());
-
-    for line in crates_lines {
-        let parsed = parse_crate_line(&line, num_crates);
-        for (j, c) in parsed.iter().enumerate() {
-            if let Some(c) = c {
-                crates[j].push(*c);
-            }
+    let stacks = if replay.is_some() {
+        step_through(&procedure, crane)
+    } else {
+        if trace.is_some() || after == Some(0) {
+            println!("{}", d5::drawing(&procedure.stacks));
         }
+        let mut done = 0;
+        d5::replay(&procedure, crane, |line, command, stacks| {
+            done += 1;
+            if trace.is_some() || after == Some(done) {
+                println!("{}: {}\n{}", line, command, d5::drawing(stacks));
+            }
+            after.is_none_or(|after| done < after)
+        })
     }
+    .unwrap_or_else(|err| cli::fail(&err, &source, &input));
+    println!("Top crates: {}", d5::top_crates(&stacks));
+}
 
-    for stack in crates.iter_mut() {
-        stack.reverse();
+// The value after `flag`, read by `parse`, exiting with the usage when it
+// doesn't read
+fn value<T>(args: &mut Vec<String>, flag: &str, expected: &str, parse: impl Fn(&str) -> Option<T>) -> Option<T> {
+    let i = args.iter().position(|arg| arg == flag)?;
+    let value = args.get(i + 1).and_then(|value| parse(value));
+    if value.is_none() {
+        eprintln!("{} needs {}\n\n{}", flag, expected, USAGE);
+        std::process::exit(2);
     }
+    args.drain(i..i + 2);
+    value
+}
 
-    for command_line in command_lines {
-        let command = parse_command_line(command_line);
-        let (from, to) = get_two_elements_mut(&mut crates, command.from-1, command.to-1);
-
-        let mut moved = from.split_off(from.len() - command.count);
-        to.append(&mut moved);
+// Draws the stacks and waits for a line on stdin before every move
+fn step_through(procedure: &Procedure, crane: Crane) -> Result<Vec<d5::Stack>, aoc::ParseError> {
+    let mut keys = io::stdin().lock().lines();
+    let mut wait = |done: usize| {
+        print!("{}/{} moves, Enter for the next one, q to stop: ", done, procedure.commands.len());
+        io::stdout().flush().ok();
+        matches!(keys.next(), Some(Ok(key)) if key.trim() != "q")
+    };
+
+    println!("{}", d5::drawing(&procedure.stacks));
+    if procedure.commands.is_empty() || !wait(0) {
+        return Ok(procedure.stacks.clone());
     }
-
-    let ans: String = crates.iter()
-        .map(|stack| stack.last().unwrap())
-        .collect();
-
-    println!("{}", ans);
-
+    let mut done = 0;
+    d5::replay(procedure, crane, |line, command, stacks| {
+        done += 1;
+        println!("{}: {}\n{}", line, command, d5::drawing(stacks));
+        done == procedure.commands.len() || wait(done)
+    })
 }
@@ -0,0 +1,17 @@
+use aoc::Rng;
+
+/// A datastream of about `size` characters. Most of it repeats a few
+/// letters, but somewhere there is a run of 14 different ones, so both
+/// markers are found.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut stream: Vec<char> = (0..size)
+        .map(|_| (b'a' + rng.below(6) as u8) as char)
+        .collect();
+
+    let mut letters: Vec<char> = ('a'..='z').collect();
+    rng.shuffle(&mut letters);
+    let at = rng.below(stream.len() + 1);
+    stream.splice(at..at, letters[..14].iter().copied());
+
+    stream.into_iter().collect::<String>() + "\n"
+}
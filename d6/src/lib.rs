@@ -0,0 +1,81 @@
+//! Day 6: Tuning Trouble. Looks for markers in the datastream.
+
+use aoc::{ParseError, Rng, Solution};
+use std::collections::HashSet;
+
+mod generate;
+
+/// Position right after the first `cnt` characters that are all different.
+pub fn solve_puzzle(line: &str, cnt: usize) -> Option<usize> {
+    let chars = line.chars().collect::<Vec<char>>();
+    for i in (cnt - 1)..chars.len() {
+        // Gets cnt chars that end and i
+        let message = chars.iter().skip(i - (cnt - 1)).take(cnt).collect::<HashSet<&char>>();
+        if message.len() == cnt {
+            return Some(i + 1);
+        }
+    }
+    None
+}
+
+/// Where the first marker of `cnt` different characters ends. The datastream
+/// is a single line of lowercase letters.
+pub fn find_marker(input: &str, cnt: usize) -> Result<usize, ParseError> {
+    let mut cursor = aoc::parse::cursors(input)
+        .next()
+        .ok_or_else(|| ParseError::end_of_input(input, "a datastream"))?;
+
+    let line = cursor.rest();
+    while !cursor.is_empty() {
+        cursor.one_of("abcdefghijklmnopqrstuvwxyz")?;
+    }
+
+    solve_puzzle(line, cnt)
+        .ok_or_else(|| cursor.error(format!("a run of {} different characters", cnt)))
+}
+
+#[test]
+fn test_part1_samples() {
+    let samples = [
+        ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7),
+        ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5),
+        ("nppdvjthqldpwncqszvftbrmjlhg", 6),
+        ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10),
+        ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11),
+    ];
+    for (line, expected) in samples.iter() {
+        assert_eq!(solve_puzzle(line, 4), Some(*expected));
+    }
+}
+
+#[test]
+fn test_part2_samples() {
+    let samples = [
+        ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 19),
+        ("bvwbjplbgvbhsrlpgdmjqwftvncz", 23),
+        ("nppdvjthqldpwncqszvftbrmjlhg", 23),
+        ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 29),
+        ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 26),
+    ];
+    for (line, expected) in samples.iter() {
+        assert_eq!(solve_puzzle(line, 14), Some(*expected));
+    }
+}
+
+/// Part 1 finds the start-of-packet marker of 4 characters, part 2 the
+/// start-of-message marker of 14.
+pub struct Day6;
+
+impl Solution for Day6 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        Ok(find_marker(input, 4)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        Ok(find_marker(input, 14)?.to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate::generate(rng, size)
+    }
+}
@@ -0,0 +1,84 @@
+use aoc::Rng;
+
+struct Directory {
+    name: String,
+    files: Vec<(String, usize)>,
+    children: Vec<usize>,
+}
+
+/// A terminal session exploring `size` directories. The disk is between 45
+/// and 60 million full, so part 2 always has to free up some space.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut directories = vec![Directory {
+        name: "/".to_string(),
+        files: Vec::new(),
+        children: Vec::new(),
+    }];
+
+    for i in 1..size.max(1) {
+        let parent = rng.below(i);
+        let name = unique_name(rng, &directories, parent, "");
+        directories.push(Directory {
+            name,
+            files: Vec::new(),
+            children: Vec::new(),
+        });
+        directories[parent].children.push(i);
+    }
+
+    let mut total = 0;
+    for i in 0..directories.len() {
+        for _ in 0..rng.below(5) {
+            let file_size = rng.between(1, 300000) as usize;
+            if total + file_size > 40_000_000 {
+                break;
+            }
+            let name = unique_name(rng, &directories, i, ".txt");
+            directories[i].files.push((name, file_size));
+            total += file_size;
+        }
+    }
+
+    let filler = rng.between(45_000_000, 60_000_000) as usize - total;
+    directories[0].files.push(("filler.dat".to_string(), filler));
+
+    let mut output = String::from("$ cd /\n");
+    session(&directories, 0, &mut output);
+    output
+}
+
+// A name that isn't used yet in `directory`
+fn unique_name(rng: &mut Rng, directories: &[Directory], directory: usize, extension: &str) -> String {
+    loop {
+        let length = rng.between(1, 8);
+        let name: String = (0..length)
+            .map(|_| (b'a' + rng.below(26) as u8) as char)
+            .collect::<String>()
+            + extension;
+
+        let taken = directories[directory].children.iter().any(|&c| directories[c].name == name)
+            || directories[directory].files.iter().any(|(file, _)| *file == name);
+        if !taken {
+            return name;
+        }
+    }
+}
+
+// Lists `directory`, then goes into every directory in it and back out
+fn session(directories: &[Directory], directory: usize, output: &mut String) {
+    let current = &directories[directory];
+
+    *output += "$ ls\n";
+    for &child in &current.children {
+        *output += &format!("dir {}\n", directories[child].name);
+    }
+    for (name, size) in &current.files {
+        *output += &format!("{} {}\n", size, name);
+    }
+
+    for &child in &current.children {
+        *output += &format!("$ cd {}\n", directories[child].name);
+        session(directories, child, output);
+        *output += "$ cd ..\n";
+    }
+}
@@ -0,0 +1,286 @@
+//! Day 7: No Space Left On Device. Works out the directory sizes from a
+//! terminal session.
+
+use aoc::{Cursor, ParseError, Rng, Solution};
+use id_tree::*;
+use std::cell::Cell;
+use std::collections::HashMap;
+
+mod generate;
+
+/// Whether a line of `ls` output is a directory or a file of some size.
+#[derive(Debug, Eq, PartialEq)]
+pub enum LsEntryType {
+    Directory,
+    File(usize),
+}
+
+/// A line of `ls` output.
+#[derive(Debug, Eq, PartialEq)]
+pub struct LsEntry {
+    pub name: String,
+    pub answer_type: LsEntryType,
+}
+
+/// A command of the session together with its output.
+#[derive(Debug, Eq, PartialEq)]
+pub enum Command {
+    Change { to: String },
+    Ls { entries: Vec<LsEntry> },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ls_entry() {
+        let samples = [
+            ("dir e", "e", LsEntryType::Directory),
+            ("2557 g", "g", LsEntryType::File(2557)),
+            ("62596 h.lst", "h.lst", LsEntryType::File(62596)),
+        ];
+        for (input, ans_name, ans_type) in samples {
+            let entry = parse_ls_entry(&mut Cursor::new(1, input)).unwrap();
+            assert_eq!(entry.name, ans_name);
+            assert_eq!(entry.answer_type, ans_type);
+        }
+    }
+
+    #[test]
+    fn test_parse_command() {
+        let samples = [
+            (
+                vec!["$ cd /home/user"],
+                Command::Change {
+                    to: "/home/user".to_string(),
+                },
+            ),
+            (vec!["$ ls"], Command::Ls { entries: vec![] }),
+            (
+                vec![
+                    "$ ls",
+                    "dir e",
+                    "2557 g",
+                    "62596 h.lst",
+                ],
+                Command::Ls {
+                    entries: vec![
+                        LsEntry {
+                            name: "e".to_string(),
+                            answer_type: LsEntryType::Directory,
+                        },
+                        LsEntry {
+                            name: "g".to_string(),
+                            answer_type: LsEntryType::File(2557),
+                        },
+                        LsEntry {
+                            name: "h.lst".to_string(),
+                            answer_type: LsEntryType::File(62596),
+                        },
+                    ],
+                },
+            ),
+        ];
+        for (input, ans) in samples {
+            let mut lines: Vec<Cursor> = input
+                .iter()
+                .enumerate()
+                .map(|(i, line)| Cursor::new(i + 1, line))
+                .collect();
+            assert_eq!(parse_command(&mut lines), Ok(ans));
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let mut lines = [Cursor::new(1, "$ ls"), Cursor::new(2, "12x f")];
+        assert_eq!(
+            parse_command(&mut lines),
+            Err(ParseError::new(2, 3, "\" \"", "\"x\"")),
+        );
+
+        let mut lines = [Cursor::new(5, "$ mv a b")];
+        assert_eq!(
+            parse_command(&mut lines),
+            Err(ParseError::new(5, 3, "\"cd\" or \"ls\"", "\"mv\"")),
+        );
+    }
+}
+
+/// A line of `ls` output, `dir <name>` or `<size> <name>`.
+pub fn parse_ls_entry(cursor: &mut Cursor) -> Result<LsEntry, ParseError> {
+    let answer_type = if cursor.rest().starts_with("dir") {
+        cursor.tag("dir")?;
+        LsEntryType::Directory
+    } else {
+        LsEntryType::File(cursor.number()?)
+    };
+
+    cursor.tag(" ")?;
+    let name = cursor.take_rest().to_string();
+
+    Ok(LsEntry { name, answer_type })
+}
+
+/// The first line is the command, the ones after it are its output.
+pub fn parse_command(lines: &mut [Cursor]) -> Result<Command, ParseError> {
+    let (command, output) = lines.split_first_mut().unwrap();
+    command.tag("$ ")?;
+
+    let start = command.clone();
+    match command.word()? {
+        "ls" => {
+            command.end()?;
+            let entries = aoc::parse::each(output.iter().cloned(), parse_ls_entry)?;
+            Ok(Command::Ls { entries })
+        }
+        "cd" => {
+            command.tag(" ")?;
+            let to = command.take_rest().to_string();
+            Ok(Command::Change { to })
+        }
+        _ => Err(start.error("\"cd\" or \"ls\"")),
+    }
+}
+
+/// All commands of the terminal session.
+pub fn get_commands(input: &str) -> Result<Vec<Command>, ParseError> {
+    let mut input_lines: Vec<Cursor> = aoc::parse::cursors(input).collect();
+
+    // there's no rsplit_inclusive in std, so we reverse the lines and then reverse them back after split
+    input_lines.reverse();
+
+    let mut commands = input_lines
+        .split_inclusive(|line| line.rest().starts_with("$"))
+        .map(|command_lines| {
+            let mut command_lines = command_lines.to_vec();
+            command_lines.reverse();
+            parse_command(&mut command_lines)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    commands.reverse();
+    Ok(commands)
+}
+
+#[derive(Debug, Eq, PartialEq)]
+enum NodeType {
+    Directory,
+    File,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+struct NodeData {
+    size: Cell<Option<usize>>,
+    node_type: NodeType,
+}
+
+/// Replays the terminal session and returns the size of every directory.
+/// The root directory comes last.
+pub fn directory_sizes(commands: Vec<Command>) -> Vec<usize> {
+    use id_tree::InsertBehavior::*;
+
+    let mut tree: Tree<NodeData> = {
+        let cell = Cell::new(None);
+        let data = NodeData {
+            size: cell,
+            node_type: NodeType::Directory,
+        };
+        TreeBuilder::new().with_root(Node::new(data)).build()
+    };
+
+    let root = tree.root_node_id().unwrap().clone();
+    let mut cur_node_id = root.clone();
+    let mut parent_to_child: HashMap<(NodeId, String), NodeId> = HashMap::new();
+
+    for command in commands {
+        match command {
+            Command::Change { to } => {
+                cur_node_id = match to.as_str() {
+                    "/" => root.clone(),
+                    ".." => tree.get(&cur_node_id).unwrap().parent().unwrap().clone(),
+                    _ => parent_to_child.get(&(cur_node_id, to)).unwrap().clone(),
+                }
+            }
+            Command::Ls { entries } => {
+                for entry in entries {
+                    let size = match entry.answer_type {
+                        LsEntryType::Directory => None,
+                        LsEntryType::File(size) => Some(size),
+                    };
+                    let node_type = match entry.answer_type {
+                        LsEntryType::Directory => NodeType::Directory,
+                        LsEntryType::File(_) => NodeType::File,
+                    };
+                    let node_data = NodeData {
+                        size: Cell::new(size),
+                        node_type,
+                    };
+                    let node = Node::new(node_data);
+                    let node_id = &mut tree.insert(node, UnderNode(&cur_node_id)).unwrap();
+                    parent_to_child.insert((cur_node_id.clone(), entry.name), node_id.clone());
+                }
+            }
+        }
+    }
+
+    let nodes = tree.traverse_post_order(&root).unwrap();
+
+    for node in nodes {
+        let data = node.data().size.get();
+
+        if data.is_none() {
+            let size = node
+                .children()
+                .iter()
+                .map(|child_id| tree.get(child_id).unwrap().data())
+                .map(|data| data.size.get().unwrap())
+                .sum::<usize>();
+
+            node.data().size.set(Some(size));
+        }
+    }
+
+    tree.traverse_post_order(&root)
+        .unwrap()
+        .filter(|node| node.data().node_type == NodeType::Directory)
+        .map(|node| node.data().size.get().unwrap())
+        .collect()
+}
+
+/// Part 1 sums the directories of at most 100000, part 2 finds the smallest
+/// one to delete to make room for the update.
+pub struct Day7;
+
+impl Solution for Day7 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        Ok(directory_sizes(get_commands(input)?)
+            .into_iter()
+            .filter(|size| *size <= 100000)
+            .sum::<usize>()
+            .to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        let sizes = directory_sizes(get_commands(input)?);
+
+        let size_total = 70000000;
+        let size_needed = 30000000;
+        let size_to_use = size_total - size_needed;
+        let size_present = *sizes.last().unwrap();
+
+        let size_to_remove = size_present - size_to_use;
+
+        let answer = sizes
+            .into_iter()
+            .filter(|size| *size >= size_to_remove)
+            .min()
+            .unwrap();
+
+        Ok(answer.to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate::generate(rng, size)
+    }
+}
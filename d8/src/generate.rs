@@ -0,0 +1,14 @@
+use aoc::Rng;
+
+/// A forest of `size` by `size` trees.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    (0..size)
+        .map(|_| {
+            (0..size)
+                .map(|_| char::from_digit(rng.below(10) as u32, 10).unwrap())
+                .collect::<String>()
+                + "\n"
+        })
+        .collect()
+}
@@ -0,0 +1,124 @@
+//! Day 8: Treetop Tree House. Looks along the rows and columns of the tree
+//! heights.
+
+use aoc::{Grid, ParseError, Point, Rng, Solution};
+
+mod generate;
+
+fn solve_one_dim_p1(view: &[i32]) -> Vec<bool> {
+    let mut max = view[0];
+    let mut answer = vec![false; view.len()];
+    for idx in 0..view.len() {
+        if view[idx] > max {
+            max = view[idx];
+            answer[idx] = true;
+        }
+    }
+
+    answer[0] = true;
+
+    answer
+}
+
+// returns number of visible trees to the left
+fn solve_one_dim_p2(view: &[i32]) -> Vec<usize> {
+    let mut answer = vec![0; view.len()];
+    for idx in 1..view.len() {
+        let mut count = 0;
+        for jdx in (0..idx).rev() {
+            count += 1;
+            if view[jdx] >= view[idx] {
+                break;
+            }
+        }
+        answer[idx] = count;
+    }
+    answer
+}
+
+#[test]
+fn test_solve_one_dim_p2() {
+    let answer = solve_one_dim_p2(&[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    assert_eq!(answer, vec![0, 1, 2, 3, 4, 5, 6, 7, 8]);
+
+    let answer = solve_one_dim_p2(&[9, 8, 7, 6, 5, 4, 3, 2, 1]);
+    assert_eq!(answer, vec![0, 1, 1, 1, 1, 1, 1, 1, 1]);
+
+    let answer = solve_one_dim_p2(&[1, 2, 3, 1, 2, 5, 1]);
+    assert_eq!(answer, vec![0, 1, 2, 1, 2, 5, 1]);
+}
+
+/// The tree heights, one digit per tree.
+pub fn parse_field(input: &str) -> Result<Grid<i32>, ParseError> {
+    Grid::parse(input, "a tree height", |c| c.to_digit(10).map(|d| d as i32))
+}
+
+// Runs `solve` along every row and column, looking in from both ends, and
+// hands every result to `apply` together with the tree it belongs to
+fn for_each_line<T>(
+    field: &Grid<i32>,
+    solve: impl Fn(&[i32]) -> Vec<T>,
+    mut apply: impl FnMut(Point, T),
+) {
+    let mut look = |heights: Vec<i32>, points: Vec<Point>| {
+        for (point, value) in points.into_iter().zip(solve(&heights)) {
+            apply(point, value);
+        }
+    };
+
+    for row in 0..field.height() {
+        let heights = field.row(row).to_vec();
+        let points: Vec<Point> = (0..field.width())
+            .map(|col| Point::new(row as i32, col as i32))
+            .collect();
+        look(heights.iter().rev().copied().collect(), points.iter().rev().copied().collect());
+        look(heights, points);
+    }
+
+    for col in 0..field.width() {
+        let heights: Vec<i32> = field.column(col).copied().collect();
+        let points: Vec<Point> = (0..field.height())
+            .map(|row| Point::new(row as i32, col as i32))
+            .collect();
+        look(heights.iter().rev().copied().collect(), points.iter().rev().copied().collect());
+        look(heights, points);
+    }
+}
+
+/// Number of trees visible from outside the grid.
+pub fn count_visible(input: &str) -> Result<usize, ParseError> {
+    let field = parse_field(input)?;
+
+    let mut visible = field.map(|_| false);
+    for_each_line(&field, solve_one_dim_p1, |point, val| visible[point] |= val);
+
+    Ok(visible.iter().filter(|(_, &v)| v).count())
+}
+
+/// Highest scenic score of any tree.
+pub fn best_scenic_score(input: &str) -> Result<usize, ParseError> {
+    let field = parse_field(input)?;
+
+    let mut scores = field.map(|_| 1);
+    for_each_line(&field, solve_one_dim_p2, |point, val| scores[point] *= val);
+
+    Ok(scores.iter().map(|(_, &score)| score).max().unwrap())
+}
+
+/// Part 1 counts the trees visible from outside, part 2 finds the best
+/// scenic score.
+pub struct Day8;
+
+impl Solution for Day8 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        Ok(count_visible(input)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        Ok(best_scenic_score(input)?.to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate::generate(rng, size)
+    }
+}
@@ -0,0 +1,8 @@
+use aoc::Rng;
+
+/// `size` moves of the head of the rope.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| format!("{} {}\n", rng.pick(&['R', 'L', 'U', 'D']), rng.between(1, 20)))
+        .collect()
+}
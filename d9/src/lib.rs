@@ -0,0 +1,124 @@
+//! Day 9: Rope Bridge. Drags the knots of a rope behind its head.
+
+use aoc::visual::Frame;
+use aoc::{Cursor, Grid, ParseError, Point, Recorder, Rng, Solution};
+use std::collections::HashSet;
+
+mod generate;
+
+fn step_one(dx: i32, dy: i32) -> (i32, i32) {
+    fn step_one_impl(dx: i32, dy: i32) -> (i32, i32) {
+        if dx > 0 && dy > 0 && (dx > 1 || dy > 1) {
+            (1, 1)
+        } else if dx > 1 {
+            (1, 0)
+        } else if dy > 1 {
+            (0, 1)
+        } else {
+            (0, 0)
+        }
+    }
+
+    let (mut dx, flip_x) = if dx < 0 { (-dx, true) } else { (dx, false) };
+
+    let (mut dy, flip_y) = if dy < 0 { (-dy, true) } else { (dy, false) };
+
+    (dx, dy) = step_one_impl(dx, dy);
+
+    if flip_x {
+        dx = -dx;
+    }
+
+    if flip_y {
+        dy = -dy;
+    }
+
+    (dx, dy)
+}
+
+/// Where knot `next` goes after the knot `prev` ahead of it moved.
+pub fn advance_next(prev: Point, next: Point) -> Point {
+    let (dx, dy) = step_one(prev.row - next.row, prev.col - next.col);
+    next.advanced(dx, dy)
+}
+
+
+// A window around the head: the knots by number, with the head as 'H', and
+// the positions the tail visited as '#'. Up is row + 1, so rows are drawn
+// from the top down.
+fn rope_frame(rope: &[Point], visited: &HashSet<Point>) -> Frame {
+    const ROWS: i32 = 10;
+    const COLS: i32 = 30;
+
+    let head = rope[0];
+    let mut frame = Grid::new(2 * ROWS as usize + 1, 2 * COLS as usize + 1, '.');
+    for point in frame.points() {
+        let position = Point::new(head.row + ROWS - point.row, head.col - COLS + point.col);
+        if let Some(i) = rope.iter().position(|knot| *knot == position) {
+            frame[point] = if i == 0 { 'H' } else { char::from_digit(i as u32 % 10, 10).unwrap() };
+        } else if visited.contains(&position) {
+            frame[point] = '#';
+        }
+    }
+    frame
+}
+
+/// A move of the head: the direction as a (row, column) step, up is row + 1,
+/// and the number of steps.
+pub fn parse_move(cursor: &mut Cursor) -> Result<((i32, i32), i32), ParseError> {
+    let direction = match cursor.one_of("UDLR")? {
+        'U' => (1, 0),
+        'D' => (-1, 0),
+        'L' => (0, -1),
+        _ => (0, 1),
+    };
+    cursor.tag(" ")?;
+    let distance = cursor.number()?;
+    Ok((direction, distance))
+}
+
+/// Number of positions the tail of a rope with `knots` knots visits. Every
+/// step of the head is a frame for `recorder`.
+pub fn count_tail_positions(input: &str, knots: usize, recorder: &mut Recorder) -> Result<usize, ParseError> {
+    let mut rope = vec![Point::new(0, 0); knots];
+    let mut visited = HashSet::new();
+    visited.insert(*rope.last().unwrap());
+
+    for ((dx, dy), distance) in aoc::parse::lines(input, parse_move)? {
+        for _ in 0..distance {
+            rope[0].row += dx;
+            rope[0].col += dy;
+
+
+            for i in 1..rope.len() {
+                rope[i] = advance_next(rope[i - 1], rope[i]);
+            }
+            visited.insert(*rope.last().unwrap());
+            recorder.capture(|| rope_frame(&rope, &visited));
+        }
+    }
+
+    Ok(visited.len())
+}
+
+/// Part 1 follows a rope of 2 knots, part 2 one of 10.
+pub struct Day9;
+
+impl Solution for Day9 {
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        Ok(count_tail_positions(input, 2, &mut Recorder::off())?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        Ok(count_tail_positions(input, 10, &mut Recorder::off())?.to_string())
+    }
+
+    fn record(&self, input: &str, recorder: &mut Recorder) -> Result<(), ParseError> {
+        count_tail_positions(input, 10, recorder)?;
+        Ok(())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate::generate(rng, size)
+    }
+}
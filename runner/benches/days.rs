@@ -0,0 +1,29 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+use std::time::Duration;
+
+// Days where a single run of a part takes seconds; they get the fewest samples
+// criterion allows instead of the default hundred.
+const SLOW_DAYS: [usize; 6] = [15, 16, 19, 20, 23, 24];
+
+fn bench_days(c: &mut Criterion) {
+    for day in 1..=runner::days() {
+        let path = runner::input_path(day);
+        let input = std::fs::read_to_string(&path)
+            .unwrap_or_else(|err| panic!("Can't read {}: {}", path.display(), err));
+        let solution = runner::solution(day).unwrap();
+
+        let mut group = c.benchmark_group(format!("d{}", day));
+        if SLOW_DAYS.contains(&day) {
+            group.sample_size(10);
+            group.warm_up_time(Duration::from_millis(1));
+        }
+
+        group.bench_function("part1", |b| b.iter(|| solution.part1(black_box(&input))));
+        group.bench_function("part2", |b| b.iter(|| solution.part2(black_box(&input))));
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_days);
+criterion_main!(benches);
@@ -0,0 +1,59 @@
+//! Every day's solution in one place, so they can be run by day number.
+
+use aoc::Solution;
+use std::path::PathBuf;
+
+const SOLUTIONS: [&dyn Solution; 25] = [
+    &d1::Day1,
+    &d2::Day2,
+    &d3::Day3,
+    &d4::Day4,
+    &d5::Day5,
+    &d6::Day6,
+    &d7::Day7,
+    &d8::Day8,
+    &d9::Day9,
+    &d10::Day10,
+    &d11::Day11,
+    &d12::Day12,
+    &d13::Day13,
+    &d14::Day14,
+    &d15::Day15,
+    &d16::Day16,
+    &d17::Day17,
+    &d18::Day18,
+    &d19::Day19,
+    &d20::Day20,
+    &d21::Day21,
+    &d22::Day22,
+    &d23::Day23,
+    &d24::Day24,
+    &d25::Day25,
+];
+
+/// The solution for `day`, counting from 1.
+pub fn solution(day: usize) -> Option<&'static dyn Solution> {
+    day.checked_sub(1).and_then(|i| SOLUTIONS.get(i)).copied()
+}
+
+/// Number of days with a solution.
+pub fn days() -> usize {
+    SOLUTIONS.len()
+}
+
+/// Where a day's puzzle input lives in the repository.
+pub fn input_path(day: usize) -> PathBuf {
+    day_dir(day).join("input")
+}
+
+/// Where the sample from a day's puzzle text lives in the repository.
+pub fn sample_path(day: usize) -> PathBuf {
+    day_dir(day).join("sample.txt")
+}
+
+/// The directory of a day's crate, which holds its puzzle input and sample.
+pub fn day_dir(day: usize) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(format!("d{}", day))
+}
@@ -0,0 +1,444 @@
+use aoc::cli::{Answers, Format};
+use aoc::input::Source;
+use aoc::{ParseError, Recorder, Rng};
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::path::PathBuf;
+use std::process;
+use std::time::{Duration, Instant};
+
+const USAGE: &str = "\
+Usage: aoc run --day <N> [--part <1|2>] [<INPUT>] [--format text|json]
+       aoc run --all [--sample] [--format text|json]
+       aoc visualize --day <N> [<INPUT>] [--every <N>] [<OUTPUT>]
+       aoc generate --day <N> [--seed <N>] [--size <N>]
+
+The input is the day's puzzle input, unless one of these says otherwise:
+       --input <PATH>  a file
+       --sample        the sample from the puzzle text
+       -               stdin
+
+-v shows the progress of slow days on stderr, -vv what they're doing as well.
+
+Outputs of visualize:
+       --play [--fps <F>]          play in the terminal (the default)
+       --asciicast <FILE> [--fps <F>]
+       --ppm <DIR> [--scale <N>]   a colour image per frame
+       --pbm <DIR> [--scale <N>]   a black and white image per frame";
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Run(Args),
+    Visualize(VisualizeArgs),
+    Generate(GenerateArgs),
+}
+
+#[derive(Debug, PartialEq)]
+struct Args {
+    days: Vec<usize>,
+    parts: Vec<u8>,
+    input: Source,
+    format: Format,
+    verbosity: u8,
+}
+
+#[derive(Debug, PartialEq)]
+enum Output {
+    Play,
+    Asciicast(PathBuf),
+    Ppm(PathBuf),
+    Pbm(PathBuf),
+}
+
+#[derive(Debug, PartialEq)]
+struct VisualizeArgs {
+    day: usize,
+    input: Source,
+    output: Output,
+    every: usize,
+    fps: f64,
+    scale: usize,
+    verbosity: u8,
+}
+
+#[derive(Debug, PartialEq)]
+struct GenerateArgs {
+    day: usize,
+    seed: u64,
+    size: usize,
+}
+
+fn parse_number<T: std::str::FromStr>(arg: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{} needs a number, got {}", arg, value))
+}
+
+fn parse_args(args: &[String]) -> Result<Command, String> {
+    let mut iter = args.iter();
+
+    let command = match iter.next().map(|s| s.as_str()) {
+        Some(command @ ("run" | "visualize" | "generate")) => command,
+        Some(command) => return Err(format!("Unknown command: {}", command)),
+        None => return Err("Missing command".into()),
+    };
+
+    let mut day = None;
+    let mut part = None;
+    let mut sources = Vec::new();
+    let mut all = false;
+    let mut outputs = Vec::new();
+    let mut every = None;
+    let mut fps = None;
+    let mut scale = None;
+    let mut format = None;
+    let mut verbosity = 0;
+    let mut seed = None;
+    let mut size = None;
+
+    while let Some(arg) = iter.next() {
+        let mut value = || {
+            iter.next()
+                .ok_or_else(|| format!("Missing value for {}", arg))
+        };
+
+        match arg.as_str() {
+            "--day" => {
+                let value = value()?;
+                let parsed = value.parse::<usize>().ok();
+                match parsed.filter(|d| runner::solution(*d).is_some()) {
+                    Some(d) => day = Some(d),
+                    None => return Err(format!("No solution for day {}", value)),
+                }
+            }
+            "--part" => {
+                let value = value()?;
+                match value.as_str() {
+                    "1" => part = Some(1),
+                    "2" => part = Some(2),
+                    _ => return Err(format!("Part must be 1 or 2, got {}", value)),
+                }
+            }
+            "--input" => sources.push(Source::Path(PathBuf::from(value()?))),
+            "--sample" => sources.push(Source::Sample),
+            "-" => sources.push(Source::Stdin),
+            "--all" => all = true,
+            "--play" => outputs.push(Output::Play),
+            "--asciicast" => outputs.push(Output::Asciicast(PathBuf::from(value()?))),
+            "--ppm" => outputs.push(Output::Ppm(PathBuf::from(value()?))),
+            "--pbm" => outputs.push(Output::Pbm(PathBuf::from(value()?))),
+            "--every" => every = Some(parse_number(arg, value()?)?),
+            "--fps" => fps = Some(parse_number(arg, value()?)?),
+            "--scale" => scale = Some(parse_number(arg, value()?)?),
+            "--seed" => seed = Some(parse_number(arg, value()?)?),
+            "--size" => size = Some(parse_number(arg, value()?)?),
+            "--format" => format = Some(value()?.parse()?),
+            "-v" | "--verbose" => verbosity += 1,
+            "-vv" => verbosity += 2,
+            _ => return Err(format!("Unknown argument: {}", arg)),
+        }
+    }
+
+    if sources.len() > 1 {
+        return Err("Only one of --input, --sample and - can be given".into());
+    }
+    let input = sources.pop();
+
+    if command == "generate" {
+        let others = [every.is_some(), fps.is_some(), scale.is_some(), format.is_some(), input.is_some()];
+        if all || part.is_some() || !outputs.is_empty() || others.contains(&true) {
+            return Err("generate takes a single --day, a --seed and a --size".into());
+        }
+        return Ok(Command::Generate(GenerateArgs {
+            day: day.ok_or("generate needs a --day")?,
+            seed: seed.unwrap_or(0),
+            size: size.unwrap_or(10),
+        }));
+    }
+    if seed.is_some() || size.is_some() {
+        return Err("--seed and --size only work with generate".into());
+    }
+
+    let single = matches!(input, Some(Source::Path(_) | Source::Stdin));
+    let input = input.unwrap_or(Source::Puzzle);
+
+    if command == "visualize" {
+        if all || part.is_some() {
+            return Err("visualize takes a single --day and no --part".into());
+        }
+        if format.is_some() {
+            return Err("--format only works with run".into());
+        }
+        let day = day.ok_or("visualize needs a --day")?;
+        if outputs.len() > 1 {
+            return Err("Only one of --play, --asciicast, --ppm and --pbm can be given".into());
+        }
+        let output = outputs.pop().unwrap_or(Output::Play);
+        if fps.is_some_and(|fps: f64| fps <= 0.0) {
+            return Err("--fps has to be more than 0".into());
+        }
+
+        return Ok(Command::Visualize(VisualizeArgs {
+            day,
+            input,
+            output,
+            every: every.unwrap_or(1),
+            fps: fps.unwrap_or(10.0),
+            scale: scale.unwrap_or(4),
+            verbosity,
+        }));
+    }
+
+    if !outputs.is_empty() || every.is_some() || fps.is_some() || scale.is_some() {
+        return Err("Output options only work with visualize".into());
+    }
+
+    let days = match (day, all) {
+        (Some(day), false) => vec![day],
+        (None, true) => (1..=runner::days()).collect(),
+        (Some(_), true) => return Err("--day and --all can't be used together".into()),
+        (None, false) => return Err("Either --day or --all is required".into()),
+    };
+
+    if all && single {
+        return Err("--input and - only work with a single --day".into());
+    }
+
+    // With --all the days that don't have the part skip it
+    if let (Some(day), Some(part)) = (day, part) {
+        if part > runner::solution(day).unwrap().parts() {
+            return Err(format!("Day {} has no part {}", day, part));
+        }
+    }
+    let parts = match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+
+    Ok(Command::Run(Args {
+        days,
+        parts,
+        input,
+        format: format.unwrap_or(Format::Text),
+        verbosity,
+    }))
+}
+
+// The input and the name to show for it in messages
+fn read_input(day: usize, input: &Source) -> (String, String) {
+    input.read(&runner::day_dir(day)).unwrap_or_else(|message| {
+        eprintln!("{}", message);
+        process::exit(1);
+    })
+}
+
+fn report_parse_error(err: ParseError, source: &str, input: &str) -> ! {
+    eprint!("{}", err.report(source, input));
+    process::exit(1);
+}
+
+fn run(args: Args) {
+    for day in args.days {
+        let (source, input) = read_input(day, &args.input);
+        let solution = runner::solution(day).unwrap();
+
+        let mut answers = Answers {
+            day,
+            part1: None,
+            part2: None,
+            elapsed: Duration::ZERO,
+        };
+
+        for part in args.parts.iter().filter(|&&part| part <= solution.parts()) {
+            let start = Instant::now();
+            let answer = match part {
+                1 => solution.part1(&input),
+                _ => solution.part2(&input),
+            };
+            let elapsed = start.elapsed();
+            answers.elapsed += elapsed;
+
+            let answer = answer.unwrap_or_else(|err| report_parse_error(err, &source, &input));
+
+            if args.format == Format::Text {
+                // Some answers are pictures, those start on their own line
+                if answer.contains('\n') {
+                    println!("Day {} part {} ({:.2?}):\n{}", day, part, elapsed, answer);
+                } else {
+                    println!("Day {} part {}: {} ({:.2?})", day, part, answer, elapsed);
+                }
+            }
+
+            match part {
+                1 => answers.part1 = Some(answer),
+                _ => answers.part2 = Some(answer),
+            }
+        }
+
+        // A line of JSON per day, so `--all` gives JSON Lines
+        if args.format == Format::Json {
+            println!("{}", answers.to_json());
+        }
+    }
+}
+
+fn visualize(args: VisualizeArgs) -> io::Result<()> {
+    let (source, input) = read_input(args.day, &args.input);
+    let solution = runner::solution(args.day).unwrap();
+
+    let mut recorder = Recorder::new().every(args.every);
+    if let Err(err) = solution.record(&input, &mut recorder) {
+        report_parse_error(err, &source, &input);
+    }
+
+    if recorder.frames().is_empty() {
+        eprintln!("Day {} has nothing to show", args.day);
+        process::exit(1);
+    }
+
+    match args.output {
+        Output::Play => recorder.play(io::stdout().lock(), args.fps),
+        Output::Asciicast(path) => recorder.write_asciicast(BufWriter::new(File::create(path)?), args.fps),
+        Output::Ppm(dir) => recorder.write_ppm(&dir, args.scale),
+        Output::Pbm(dir) => recorder.write_pbm(&dir, args.scale),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let command = match parse_args(&args) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            process::exit(2);
+        }
+    };
+
+    match &command {
+        Command::Run(args) => aoc::log::set_verbosity(args.verbosity),
+        Command::Visualize(args) => aoc::log::set_verbosity(args.verbosity),
+        Command::Generate(_) => (),
+    }
+
+    match command {
+        Command::Run(args) => run(args),
+        Command::Visualize(args) => {
+            if let Err(err) = visualize(args) {
+                eprintln!("Can't write the frames: {}", err);
+                process::exit(1);
+            }
+        }
+        Command::Generate(args) => {
+            let solution = runner::solution(args.day).unwrap();
+            print!("{}", solution.generate(&mut Rng::new(args.seed), args.size));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> Result<Command, String> {
+        let args: Vec<String> = args.split_whitespace().map(String::from).collect();
+        parse_args(&args)
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            parse("run --day 15 --part 2 --input foo.txt"),
+            Ok(Command::Run(Args {
+                days: vec![15],
+                parts: vec![2],
+                input: Source::Path(PathBuf::from("foo.txt")),
+                format: Format::Text,
+                verbosity: 0,
+            }))
+        );
+
+        let Ok(Command::Run(json)) = parse("run --day 10 --format json -vv") else {
+            panic!("run --day 10 is a run");
+        };
+        assert_eq!((json.format, json.verbosity), (Format::Json, 2));
+
+        let Ok(Command::Run(all)) = parse("run --all") else {
+            panic!("run --all is a run");
+        };
+        assert_eq!(all.days, (1..=25).collect::<Vec<_>>());
+        assert_eq!(all.parts, vec![1, 2]);
+        assert_eq!(all.input, Source::Puzzle);
+
+        let Ok(Command::Run(samples)) = parse("run --all --sample") else {
+            panic!("run --all --sample is a run");
+        };
+        assert_eq!(samples.input, Source::Sample);
+
+        let Ok(Command::Run(stdin)) = parse("run --day 3 -") else {
+            panic!("run --day 3 - is a run");
+        };
+        assert_eq!(stdin.input, Source::Stdin);
+
+        assert!(parse("").is_err());
+        assert!(parse("run").is_err());
+        assert!(parse("run --day 26").is_err());
+        assert!(parse("run --day 1 --part 3").is_err());
+        assert!(parse("run --day 25 --part 2").is_err());
+        assert!(parse("run --day 25 --part 1").is_ok());
+        assert!(parse("run --all --part 2").is_ok());
+        assert!(parse("run --day 1 --all").is_err());
+        assert!(parse("run --all --input foo.txt").is_err());
+        assert!(parse("run --all -").is_err());
+        assert!(parse("run --day 1 --sample --input foo.txt").is_err());
+        assert!(parse("run --day").is_err());
+        assert!(parse("run --day 1 --play").is_err());
+        assert!(parse("run --day 1 --format yaml").is_err());
+    }
+
+    #[test]
+    fn test_parse_visualize_args() {
+        assert_eq!(
+            parse("visualize --day 17 --asciicast rocks.cast --fps 30 --every 2"),
+            Ok(Command::Visualize(VisualizeArgs {
+                day: 17,
+                input: Source::Puzzle,
+                output: Output::Asciicast(PathBuf::from("rocks.cast")),
+                every: 2,
+                fps: 30.0,
+                scale: 4,
+                verbosity: 0,
+            }))
+        );
+
+        let Ok(Command::Visualize(args)) = parse("visualize --day 9") else {
+            panic!("visualize --day 9 is a visualize");
+        };
+        assert_eq!(args.output, Output::Play);
+
+        assert!(parse("visualize --all").is_err());
+        assert!(parse("visualize --day 9 --part 1").is_err());
+        assert!(parse("visualize --day 9 --play --pbm frames").is_err());
+        assert!(parse("visualize --day 9 --fps 0").is_err());
+        assert!(parse("visualize --day 9 --scale x").is_err());
+        assert!(parse("visualize --day 9 --format json").is_err());
+        assert!(parse("visualize --day 9 --seed 3").is_err());
+    }
+
+    #[test]
+    fn test_parse_generate_args() {
+        assert_eq!(
+            parse("generate --day 24 --seed 7 --size 30"),
+            Ok(Command::Generate(GenerateArgs { day: 24, seed: 7, size: 30 }))
+        );
+        assert_eq!(
+            parse("generate --day 1"),
+            Ok(Command::Generate(GenerateArgs { day: 1, seed: 0, size: 10 }))
+        );
+
+        assert!(parse("generate").is_err());
+        assert!(parse("generate --all").is_err());
+        assert!(parse("generate --day 1 --seed x").is_err());
+        assert!(parse("generate --day 1 --sample").is_err());
+        assert!(parse("run --day 1 --size 5").is_err());
+    }
+}
@@ -0,0 +1,93 @@
+//! Runs every day on its sample and puzzle input and compares the results
+//! with the answers recorded in `answers.toml`.
+//!
+//! The puzzle inputs of the slowest days take up to two minutes, but they
+//! guard the solutions most worth refactoring, so they run with the rest.
+
+use std::path::{Path, PathBuf};
+
+fn answers() -> toml::Table {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../answers.toml");
+    std::fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("Can't read {}: {}", path.display(), err))
+        .parse()
+        .unwrap()
+}
+
+fn expected(day: usize, kind: &str) -> [String; 2] {
+    let answers = answers();
+    let parts = answers
+        .get(&format!("d{}", day))
+        .and_then(|day| day.get(kind))
+        .and_then(|parts| parts.as_array())
+        .unwrap_or_else(|| panic!("No {} answers for day {}", kind, day));
+
+    let part = |i: usize| parts[i].as_str().unwrap().to_string();
+    [part(0), part(1)]
+}
+
+fn check(day: usize, kind: &str, path: PathBuf) {
+    let input = std::fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("Can't read {}: {}", path.display(), err));
+    let solution = runner::solution(day).unwrap();
+    let [part1, part2] = expected(day, kind);
+
+    assert_eq!(solution.part1(&input), Ok(part1), "day {} part 1 on {}", day, kind);
+    assert_eq!(solution.part2(&input), Ok(part2), "day {} part 2 on {}", day, kind);
+}
+
+macro_rules! answer_tests {
+    ($($day:literal: $sample:ident, $input:ident;)*) => {
+        $(
+            #[test]
+            fn $sample() {
+                check($day, "sample", runner::sample_path($day));
+            }
+
+            #[test]
+            fn $input() {
+                check($day, "input", runner::input_path($day));
+            }
+        )*
+    };
+}
+
+answer_tests! {
+    1: d1_sample, d1_input;
+    2: d2_sample, d2_input;
+    3: d3_sample, d3_input;
+    4: d4_sample, d4_input;
+    5: d5_sample, d5_input;
+    6: d6_sample, d6_input;
+    7: d7_sample, d7_input;
+    8: d8_sample, d8_input;
+    9: d9_sample, d9_input;
+    10: d10_sample, d10_input;
+    11: d11_sample, d11_input;
+    12: d12_sample, d12_input;
+    13: d13_sample, d13_input;
+    14: d14_sample, d14_input;
+    15: d15_sample, d15_input;
+    16: d16_sample, d16_input;
+    17: d17_sample, d17_input;
+    18: d18_sample, d18_input;
+    19: d19_sample, d19_input;
+    20: d20_sample, d20_input;
+    21: d21_sample, d21_input;
+    22: d22_sample, d22_input;
+    23: d23_sample, d23_input;
+    24: d24_sample, d24_input;
+    25: d25_sample, d25_input;
+}
+
+#[test]
+fn test_every_day_has_answers() {
+    let answers = answers();
+    for day in 1..=runner::days() {
+        for kind in ["sample", "input"] {
+            let parts = answers[&format!("d{}", day)][kind].as_array().unwrap();
+            assert_eq!(parts.len(), 2, "day {} {}", day, kind);
+        }
+    }
+    assert_eq!(answers.len(), runner::days());
+}
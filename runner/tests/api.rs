@@ -0,0 +1,47 @@
+//! Calls the days' own functions and types from outside their crates, the
+//! way other tools would, rather than going through `Solution`.
+
+use aoc::{Cursor, ParseError};
+
+fn sample(day: usize) -> String {
+    let path = format!("{}/../d{}/sample.txt", env!("CARGO_MANIFEST_DIR"), day);
+    std::fs::read_to_string(&path).unwrap_or_else(|err| panic!("Can't read {}: {}", path, err))
+}
+
+#[test]
+fn d13_packets() {
+    let packet = |s| d13::parse(&mut Cursor::new(1, s)).unwrap();
+    assert!(packet("[1,[2,[3]]]") < packet("[[1],4]"));
+    assert_eq!(packet("[[],[5,[6]]]").to_string(), "[[],[5,[6]]]");
+    assert_eq!(d13::parse_packets(&sample(13)).unwrap().len(), 16);
+}
+
+#[test]
+fn d15_coverage() {
+    let measurements = aoc::parse::lines(&sample(15), d15::parse_message).unwrap();
+    assert_eq!(d15::find_positions_fast(&measurements, 10), vec![-2..25]);
+    assert_eq!(d15::find_answer_fast(&measurements, 10), 26);
+    assert_eq!(d15::find_tuning_frequency(&measurements, 20), 56000011);
+}
+
+#[test]
+fn d17_tower() {
+    let air = d17::parse_air(&sample(17)).unwrap();
+    let figures = d17::figures();
+    let mut tower = d17::Tower::new();
+    for _ in 0..2022 {
+        tower.drop_rock(&air, &figures, &mut aoc::Recorder::off());
+    }
+    assert_eq!(tower.height(), 3068);
+}
+
+#[test]
+fn d25_snafu() {
+    let snafu: d25::SNAFU = "2=-01".parse().unwrap();
+    assert_eq!(i64::from(&snafu), 976);
+    assert_eq!(d25::SNAFU::from(976i64), snafu);
+    assert_eq!(
+        "2=3".parse::<d25::SNAFU>(),
+        Err(ParseError::new(1, 3, "one of '=', '-', '0', '1', '2'", "\"3\""))
+    );
+}
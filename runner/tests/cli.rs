@@ -0,0 +1,38 @@
+//! Runs the `aoc` binary the way a script would and checks what it prints.
+
+use std::process::{Command, Output};
+
+fn aoc(args: &str) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(args.split_whitespace())
+        .output()
+        .unwrap_or_else(|err| panic!("Can't run aoc {}: {}", args, err))
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn test_json_without_part_2() {
+    // Day 25 only has the one part, so there is no answer for part 2, the
+    // same as the day binary gives
+    let output = aoc("run --day 25 --sample --format json");
+    assert!(output.status.success());
+    let json = stdout(&output);
+    assert_eq!(json.lines().count(), 1);
+    assert!(json.starts_with(r#"{"day": 25, "part1": "2=-1=0", "part2": null, "elapsed_ms": "#), "{}", json);
+
+    let output = aoc("run --day 25 --part 2 --sample");
+    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(stdout(&output), "");
+}
+
+#[test]
+fn test_text_without_part_2() {
+    let output = aoc("run --day 25 --sample");
+    assert!(output.status.success());
+    let text = stdout(&output);
+    assert!(text.starts_with("Day 25 part 1: 2=-1=0 ("), "{}", text);
+    assert!(!text.contains("part 2"), "{}", text);
+}
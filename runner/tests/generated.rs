@@ -0,0 +1,64 @@
+//! Runs every day on inputs its generator makes up, checking that the
+//! solutions get through them without a parse error or a panic, and that the
+//! generators give the same input for the same seed.
+
+use aoc::Rng;
+
+// Small enough that even the slow days get through a few of them quickly
+const SIZE: usize = 6;
+const SEEDS: u64 = 5;
+
+fn check(day: usize) {
+    let solution = runner::solution(day).unwrap();
+
+    // Part 2 of day 19 takes seconds even for a few blueprints
+    let seeds = if day == 19 { 1 } else { SEEDS };
+
+    for seed in 0..seeds {
+        let input = solution.generate(&mut Rng::new(seed), SIZE);
+        assert_eq!(input, solution.generate(&mut Rng::new(seed), SIZE), "day {} seed {}", day, seed);
+
+        let context = format!("day {} seed {} on\n{}", day, seed, input);
+        assert!(solution.part1(&input).is_ok(), "part 1 of {}", context);
+        assert!(solution.part2(&input).is_ok(), "part 2 of {}", context);
+    }
+}
+
+macro_rules! generated_tests {
+    ($($day:literal: $name:ident;)*) => {
+        $(
+            #[test]
+            fn $name() {
+                check($day);
+            }
+        )*
+    };
+}
+
+generated_tests! {
+    1: d1_generated;
+    2: d2_generated;
+    3: d3_generated;
+    4: d4_generated;
+    5: d5_generated;
+    6: d6_generated;
+    7: d7_generated;
+    8: d8_generated;
+    9: d9_generated;
+    10: d10_generated;
+    11: d11_generated;
+    12: d12_generated;
+    13: d13_generated;
+    14: d14_generated;
+    15: d15_generated;
+    16: d16_generated;
+    17: d17_generated;
+    18: d18_generated;
+    19: d19_generated;
+    20: d20_generated;
+    21: d21_generated;
+    22: d22_generated;
+    23: d23_generated;
+    24: d24_generated;
+    25: d25_generated;
+}